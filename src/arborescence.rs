@@ -0,0 +1,280 @@
+//! Implements [minimum spanning arborescence](http://en.wikipedia.org/wiki/Edmonds%27_algorithm)
+//! algorithms, the counterpart of minimum spanning trees for directed graphs.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use graph::Graph;
+use weight::Weight;
+
+/// Nodes that can't be reached from the root, so that no arborescence spans them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Unreachable<N>(pub Vec<N>);
+
+/// Given a directed graph G with weights on the edges and a root, returns the total weight and
+/// the edges of a minimum arborescence rooted there: a set of edges with exactly one edge into
+/// every node other than the root, through which every node can be reached from the root.
+///
+/// If some nodes can't be reached from the root at all, returns them instead.
+pub trait MinimumArborescence<G: Graph> where G::EdgeValue: Weight {
+    #[allow(clippy::type_complexity)]
+    fn minimum_arborescence(&self, graph: &G, root: G::NodeIndex)
+                            -> Result<(G::EdgeValue, Vec<(G::NodeIndex, G::NodeIndex)>),
+                                      Unreachable<G::NodeIndex>>;
+}
+
+/// [Chu-Liu/Edmonds](http://en.wikipedia.org/wiki/Edmonds%27_algorithm) -- runs in O(VE). Every
+/// node picks its lightest incoming edge; if those form a cycle, the cycle is contracted into a
+/// single node whose incoming edges are charged only the difference from the edge they would
+/// replace, and the smaller graph is solved the same way.
+pub struct ChuLiuEdmonds;
+
+// A contracted level: for each of its edges, the position of the edge it came from in the level
+// below along with the cycle and node it enters there, if any, and each cycle as its nodes paired
+// with their lightest incoming edges.
+struct Contraction {
+    origin: Vec<(usize, Option<(usize, usize)>)>,
+    cycles: Vec<Vec<(usize, usize)>>,
+}
+
+// Solves the problem on n dense nodes where every node is reachable from the root, returning
+// the positions in `edges` of the chosen edges. Contractions can nest as deep as there are
+// nodes, so they are kept on a stack and expanded after the loop.
+fn contract<E: Weight>(n: usize, root: usize, edges: &[(usize, usize, E)]) -> Vec<usize> {
+    let (mut n, mut root, mut edges) = (n, root, edges.to_vec());
+    let mut stack: Vec<Contraction> = Vec::new();
+    let mut chosen: Vec<usize> = loop {
+        let mut lightest: Vec<Option<usize>> = vec![None; n];
+        for (k, &(u, v, weight)) in edges.iter().enumerate() {
+            if u == v || v == root { continue; }
+            if lightest[v].is_none_or(|best| weight.less_than(edges[best].2)) {
+                lightest[v] = Some(k);
+            }
+        }
+
+        // Follow the lightest edges backwards from every node to find the cycles among them.
+        let mut component: Vec<Option<usize>> = vec![None; n];
+        let mut visited: Vec<Option<usize>> = vec![None; n];
+        let mut cycles: Vec<Vec<(usize, usize)>> = Vec::new();
+        for start in 0..n {
+            let mut u = start;
+            while u != root && visited[u].is_none() {
+                visited[u] = Some(start);
+                u = edges[lightest[u].unwrap()].0;
+            }
+            if u != root && visited[u] == Some(start) && component[u].is_none() {
+                let mut cycle = vec![(u, lightest[u].unwrap())];
+                let mut v = edges[lightest[u].unwrap()].0;
+                while v != u {
+                    cycle.push((v, lightest[v].unwrap()));
+                    v = edges[lightest[v].unwrap()].0;
+                }
+                for &(v, _) in cycle.iter() { component[v] = Some(cycles.len()); }
+                cycles.push(cycle);
+            }
+        }
+
+        if cycles.is_empty() {
+            break (0..n).filter(|&v| v != root).map(|v| lightest[v].unwrap()).collect();
+        }
+
+        // Cycles become nodes 0 to c - 1 and every other node gets a number after them.
+        let mut next = cycles.len();
+        let label: Vec<usize> = (0..n)
+            .map(|v| component[v].unwrap_or_else(|| { next += 1; next - 1 }))
+            .collect();
+
+        let mut contracted = Vec::new();
+        let mut origin = Vec::new();
+        for (k, &(u, v, weight)) in edges.iter().enumerate() {
+            if label[u] == label[v] { continue; }
+            let weight = match component[v] {
+                Some(_) => weight - edges[lightest[v].unwrap()].2,
+                None => weight,
+            };
+            contracted.push((label[u], label[v], weight));
+            origin.push((k, component[v].map(|c| (c, v))));
+        }
+
+        stack.push(Contraction { origin, cycles });
+        root = label[root];
+        n = next;
+        edges = contracted;
+    };
+
+    // The edge chosen into each cycle replaces the cycle's edge into the same node.
+    while let Some(Contraction { origin, cycles }) = stack.pop() {
+        let mut entered: Vec<Option<usize>> = vec![None; cycles.len()];
+        chosen = chosen.into_iter()
+            .map(|k| {
+                let (k, into) = origin[k];
+                if let Some((c, v)) = into { entered[c] = Some(v); }
+                k
+            })
+            .collect();
+        for (c, cycle) in cycles.iter().enumerate() {
+            for &(v, k) in cycle.iter() {
+                if Some(v) != entered[c] { chosen.push(k); }
+            }
+        }
+    }
+
+    chosen
+}
+
+impl<G: Graph> MinimumArborescence<G> for ChuLiuEdmonds
+    where G::NodeIndex: Hash + Eq, G::EdgeValue: Weight
+{
+    fn minimum_arborescence(&self, graph: &G, root: G::NodeIndex)
+                            -> Result<(G::EdgeValue, Vec<(G::NodeIndex, G::NodeIndex)>),
+                                      Unreachable<G::NodeIndex>>
+    {
+        let nodes = graph.nodes();
+        let n = nodes.len();
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let graph_edges = graph.edges();
+        let edges: Vec<(usize, usize, G::EdgeValue)> = graph_edges.iter()
+            .map(|&(from, to, &weight)| (index[&from], index[&to], weight))
+            .collect();
+        let r = index[&root];
+
+        let mut adjacent = vec![Vec::new(); n];
+        for &(u, v, _) in edges.iter() {
+            adjacent[u].push(v);
+        }
+        let mut reached = vec![false; n];
+        let mut queue = VecDeque::new();
+        reached[r] = true;
+        queue.push_back(r);
+        while let Some(u) = queue.pop_front() {
+            for &v in adjacent[u].iter() {
+                if !reached[v] {
+                    reached[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+        if reached.iter().any(|&r| !r) {
+            let missing = (0..n).filter(|&u| !reached[u]).map(|u| nodes[u]).collect();
+            return Err(Unreachable(missing));
+        }
+
+        let chosen = contract(n, r, &edges);
+        let total = chosen.iter().fold(G::EdgeValue::zero(), |acc, &k| acc + edges[k].2);
+        let mut tree: Vec<_> = chosen.into_iter()
+            .map(|k| (graph_edges[k].0, graph_edges[k].1))
+            .collect();
+        tree.sort_by_key(|&(_, to)| index[&to]);
+        Ok((total, tree))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+
+    #[test]
+    fn simple() {
+        // The cheap edges 1 -> 2 -> 3 -> 1 form a cycle, which has to be broken where the edge
+        // from the root comes in.
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..5).map(|_| graph.add_node(())).collect();
+        let edges = [(0, 1, 10), (0, 2, 6), (1, 2, 1), (2, 3, 1), (3, 1, 1), (3, 4, 2), (1, 4, 5)];
+        for &(from, to, weight) in edges.iter() {
+            graph.add_edge(v[from], v[to], weight);
+        }
+
+        let (total, mut tree) = ChuLiuEdmonds.minimum_arborescence(&graph, v[0]).unwrap();
+        tree.sort();
+        assert_eq!(total, 6 + 1 + 1 + 2);
+        assert_eq!(tree, vec![(v[0], v[2]), (v[2], v[3]), (v[3], v[1]), (v[3], v[4])]);
+    }
+
+    #[test]
+    fn unreachable() {
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 1);
+        graph.add_edge(v[2], v[0], 1);
+        graph.add_edge(v[2], v[3], 1);
+
+        let Unreachable(mut missing) = ChuLiuEdmonds.minimum_arborescence(&graph, v[0])
+            .unwrap_err();
+        missing.sort();
+        assert_eq!(missing, vec![v[2], v[3]]);
+        assert!(ChuLiuEdmonds.minimum_arborescence(&graph, v[2]).is_ok());
+    }
+
+    #[test]
+    fn long_chain() {
+        // Every level only contracts the last two nodes of the chain into one.
+        let n = 4000;
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+        for i in 1..n {
+            graph.add_edge(v[i - 1], v[i], 10);
+            graph.add_edge(v[i], v[i - 1], 1);
+        }
+
+        let (total, mut tree) = ChuLiuEdmonds.minimum_arborescence(&graph, v[0]).unwrap();
+        tree.sort();
+        assert_eq!(total, 10 * (n as u32 - 1));
+        assert_eq!(tree, (1..n).map(|i| (v[i - 1], v[i])).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn random() {
+        // Compare against trying every choice of one incoming edge per node.
+        for _ in 0..100 {
+            let n = 5;
+            let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+            for _ in 0..n { graph.add_node(()); }
+            for _ in 0..14 {
+                let (from, to) = (rand::random::<usize>() % n, rand::random::<usize>() % n);
+                graph.add_edge(from, to, rand::random::<u32>() % 10);
+            }
+
+            let mut incoming = vec![Vec::new(); n];
+            for (from, to, &weight) in graph.edges().into_iter() {
+                if from != to && to != 0 { incoming[to].push((from, weight)); }
+            }
+
+            let mut best: Option<u32> = None;
+            let mut choice = vec![0; n];
+            loop {
+                if (1..n).all(|v| !incoming[v].is_empty()) {
+                    let parent = |v: usize| incoming[v][choice[v]].0;
+                    let reaches_root = (1..n).all(|v| {
+                        let mut u = v;
+                        for _ in 0..n { if u != 0 { u = parent(u); } }
+                        u == 0
+                    });
+                    if reaches_root {
+                        let total = (1..n).map(|v| incoming[v][choice[v]].1).sum::<u32>();
+                        best = Some(best.map_or(total, |b| ::std::cmp::min(b, total)));
+                    }
+                }
+
+                // Advance to the next combination of choices.
+                let mut v = 1;
+                while v < n && choice[v] + 1 >= incoming[v].len() {
+                    choice[v] = 0;
+                    v += 1;
+                }
+                if v == n { break; }
+                choice[v] += 1;
+            }
+
+            match ChuLiuEdmonds.minimum_arborescence(&graph, 0) {
+                Ok((total, tree)) => {
+                    assert_eq!(Some(total), best);
+                    assert_eq!(tree.len(), n - 1);
+                }
+                Err(_) => assert!(best.is_none()),
+            }
+        }
+    }
+}
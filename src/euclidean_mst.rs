@@ -0,0 +1,573 @@
+//! Implements the
+//! [Euclidean minimum spanning tree](http://en.wikipedia.org/wiki/Euclidean_minimum_spanning_tree)
+//! of points in the plane.
+
+use std::cmp::Ordering;
+
+use graph::{AdjacencyList, Graph};
+use mst::{Kruskals, MinimumSpanningTree};
+use point::Point;
+
+/// Given a set of points P, returns the total length and the edges of a spanning tree of P in
+/// which the distances between the ends of the edges add up to as little as possible. Edges are
+/// pairs of positions in P.
+pub trait EuclideanMinimumSpanningTree {
+    fn euclidean_mst(&self, points: &[Point<f32>]) -> (f32, Vec<(usize, usize)>);
+}
+
+/// Restricts the candidate edges to a
+/// [Delaunay triangulation](http://en.wikipedia.org/wiki/Delaunay_triangulation), which always
+/// contains an MST of the complete graph, and runs Kruskal's algorithm on its O(n) edges -- runs
+/// in O(n log n) on all but adversarial inputs.
+///
+/// The triangulation is built with the sweep-hull method used by Delaunator: points are added in
+/// order of their distance from a seed triangle, each joined to the part of the convex hull it
+/// can see, and edges are flipped until every triangle's circumcircle is empty again. Inputs too
+/// close to a line for the sweep to order them fall back to Prim's algorithm in O(n^2).
+pub struct Delaunay;
+
+const EMPTY: usize = usize::MAX;
+
+// Rounded determinants can get the sign wrong for points that are nearly collinear or cocircular,
+// so the predicates below redo them exactly whenever the rounding error could have flipped it,
+// after Shewchuk's [robust predicates](https://www.cs.cmu.edu/~quake/robust.html). Exact values
+// are expansions: sums of non-overlapping f64s in increasing order of magnitude.
+const EPSILON: f64 = f64::EPSILON / 2.0;
+
+// The rounded sum of a and b, and the rounding error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let (bv, av) = (x - a, x - (x - a));
+    (x, (a - av) + (b - bv))
+}
+
+// The rounded product of a and b, and the rounding error.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+fn difference(a: f64, b: f64) -> Vec<f64> {
+    let (x, error) = two_sum(a, -b);
+    vec![error, x]
+}
+
+fn sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut h = e.to_vec();
+    for &b in f.iter() {
+        let mut grown = Vec::with_capacity(h.len() + 1);
+        let mut q = b;
+        for &x in h.iter() {
+            let (s, error) = two_sum(q, x);
+            if error != 0.0 { grown.push(error); }
+            q = s;
+        }
+        grown.push(q);
+        h = grown;
+    }
+    h
+}
+
+fn scale(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(2 * e.len() + 1);
+    let mut q = 0.0;
+    for &x in e.iter() {
+        let (product, error) = two_product(x, b);
+        let (s, low) = two_sum(q, error);
+        let (s, high) = two_sum(product, s);
+        h.extend([low, high].iter().filter(|&&y| y != 0.0));
+        q = s;
+    }
+    h.push(q);
+    h
+}
+
+fn product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(vec![0.0], |acc, &b| sum(&acc, &scale(e, b)))
+}
+
+// The sign of an expansion is the sign of its largest nonzero part, as -1, 0 or 1.
+fn sign(e: &[f64]) -> f64 {
+    e.iter().rev().find(|&&x| x != 0.0).map_or(0.0, |x| x.signum())
+}
+
+// Positive if the triangle abc turns counterclockwise, negative if it turns clockwise, and zero
+// only if a, b and c are exactly on a line.
+fn orientation(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    let left = (a.0 - c.0) * (b.1 - c.1);
+    let right = (a.1 - c.1) * (b.0 - c.0);
+    let det = left - right;
+    let bound = (3.0 + 16.0 * EPSILON) * EPSILON * (left.abs() + right.abs());
+    if det > bound || -det > bound { return det; }
+
+    let (acx, acy) = (difference(a.0, c.0), difference(a.1, c.1));
+    let (bcx, bcy) = (difference(b.0, c.0), difference(b.1, c.1));
+    sign(&sum(&product(&acx, &bcy), &scale(&product(&acy, &bcx), -1.0)))
+}
+
+// Whether p is strictly inside the circumcircle of the counterclockwise triangle abc.
+fn in_circle(a: (f64, f64), b: (f64, f64), c: (f64, f64), p: (f64, f64)) -> bool {
+    let (adx, ady) = (a.0 - p.0, a.1 - p.1);
+    let (bdx, bdy) = (b.0 - p.0, b.1 - p.1);
+    let (cdx, cdy) = (c.0 - p.0, c.1 - p.1);
+    let (bc, cb) = (bdx * cdy, cdx * bdy);
+    let (ca, ac) = (cdx * ady, adx * cdy);
+    let (ab, ba) = (adx * bdy, bdx * ady);
+    let (al, bl, cl) = (adx * adx + ady * ady, bdx * bdx + bdy * bdy, cdx * cdx + cdy * cdy);
+    let det = al * (bc - cb) + bl * (ca - ac) + cl * (ab - ba);
+    let permanent = (bc.abs() + cb.abs()) * al + (ca.abs() + ac.abs()) * bl +
+        (ab.abs() + ba.abs()) * cl;
+    let bound = (10.0 + 96.0 * EPSILON) * EPSILON * permanent;
+    if det > bound || -det > bound { return det > 0.0; }
+
+    let d = [(a.0, a.1), (b.0, b.1), (c.0, c.1)].iter()
+        .map(|&(x, y)| (difference(x, p.0), difference(y, p.1)))
+        .collect::<Vec<_>>();
+    let lift = |i: usize| sum(&product(&d[i].0, &d[i].0), &product(&d[i].1, &d[i].1));
+    let cross = |i: usize, j: usize| {
+        sum(&product(&d[i].0, &d[j].1), &scale(&product(&d[i].1, &d[j].0), -1.0))
+    };
+    let det = sum(&sum(&product(&lift(0), &cross(1, 2)), &product(&lift(1), &cross(2, 0))),
+                  &product(&lift(2), &cross(0, 1)));
+    sign(&det) > 0.0
+}
+
+// The center of the circle through a, b and c, which is infinitely far away when they're on a
+// line.
+fn circumcenter(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (ex, ey) = (c.0 - a.0, c.1 - a.1);
+    let (bl, cl) = (dx * dx + dy * dy, ex * ex + ey * ey);
+    let d = 0.5 / (dx * ey - dy * ex);
+    (a.0 + (ey * bl - dy * cl) * d, a.1 + (dx * cl - ex * bl) * d)
+}
+
+fn distance_squared(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0) * (a.0 - b.0) + (a.1 - b.1) * (a.1 - b.1)
+}
+
+// Increases with the angle of (dx, dy) like atan2 does, going from 0 to 1 around the circle.
+fn pseudo_angle(dx: f64, dy: f64) -> f64 {
+    let p = dx / (dx.abs() + dy.abs());
+    if dy > 0.0 { (3.0 - p) / 4.0 } else { (1.0 + p) / 4.0 }
+}
+
+// A triangulation in progress. Triangle t has the corners triangles[3t..3t + 3] in
+// counterclockwise order, and half-edge h goes from corner h to the next corner of the same
+// triangle, with halfedges[h] the half-edge going the other way in the neighbouring triangle.
+// The convex hull is a counterclockwise linked list, where hull_tri[v] is the half-edge from v to
+// hull_next[v], and removed vertices point at themselves.
+struct Triangulation<'a> {
+    coords: &'a [(f64, f64)],
+    triangles: Vec<usize>,
+    halfedges: Vec<usize>,
+    hull_next: Vec<usize>,
+    hull_prev: Vec<usize>,
+    hull_tri: Vec<usize>,
+    hull_start: usize,
+    hash: Vec<usize>,
+    center: (f64, f64),
+}
+
+impl<'a> Triangulation<'a> {
+    fn hash_key(&self, p: (f64, f64)) -> usize {
+        let angle = pseudo_angle(p.0 - self.center.0, p.1 - self.center.1);
+        (angle * self.hash.len() as f64) as usize % self.hash.len()
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        self.halfedges[a] = b;
+        if b != EMPTY {
+            self.halfedges[b] = a;
+        }
+    }
+
+    fn add_triangle(&mut self, i0: usize, i1: usize, i2: usize, a: usize, b: usize, c: usize)
+                    -> usize {
+        let t = self.triangles.len();
+        self.triangles.extend_from_slice(&[i0, i1, i2]);
+        self.halfedges.extend_from_slice(&[EMPTY, EMPTY, EMPTY]);
+        self.link(t, a);
+        self.link(t + 1, b);
+        self.link(t + 2, c);
+        t
+    }
+
+    // Flips the edge of half-edge a, and then the edges that flip exposes, until they are all
+    // Delaunay. Returns the half-edge that now starts at the corner of a's triangle opposite a.
+    //
+    //           pl                    pl
+    //          /||\                  /  \
+    //       al/ || \bl            al/    \a
+    //        /  ||  \              /      \
+    //       /  a||b  \    flip    /___ar___\
+    //     p0\   ||   /p1   =>   p0\---bl---/p1
+    //        \  ||  /              \      /
+    //       ar\ || /br             b\    /br
+    //          \||/                  \  /
+    //           pr                    pr
+    fn legalize(&mut self, a: usize) -> usize {
+        let mut stack = Vec::new();
+        let mut a = a;
+        loop {
+            let b = self.halfedges[a];
+            let a0 = a - a % 3;
+            let ar = a0 + (a + 2) % 3;
+
+            let flipped = b != EMPTY && {
+                let b0 = b - b % 3;
+                let al = a0 + (a + 1) % 3;
+                let bl = b0 + (b + 2) % 3;
+                let (p0, pr, pl, p1) = (self.triangles[ar], self.triangles[a],
+                                        self.triangles[al], self.triangles[bl]);
+                let illegal = in_circle(self.coords[p0], self.coords[pr], self.coords[pl],
+                                        self.coords[p1]);
+                if illegal {
+                    self.triangles[a] = p1;
+                    self.triangles[b] = p0;
+
+                    // The flipped edge may have been on the hull, which has to follow it.
+                    let hbl = self.halfedges[bl];
+                    if hbl == EMPTY {
+                        let mut e = self.hull_start;
+                        loop {
+                            if self.hull_tri[e] == bl {
+                                self.hull_tri[e] = a;
+                                break;
+                            }
+                            e = self.hull_prev[e];
+                            if e == self.hull_start { break; }
+                        }
+                    }
+                    let har = self.halfedges[ar];
+                    self.link(a, hbl);
+                    self.link(b, har);
+                    self.link(ar, bl);
+                    stack.push(b0 + (b + 1) % 3);
+                }
+                illegal
+            };
+
+            if !flipped {
+                match stack.pop() {
+                    Some(next) => a = next,
+                    None => return ar,
+                }
+            }
+        }
+    }
+
+    // Adds point i outside of the current hull. Returns false if it can't see any of the hull,
+    // which only happens when rounding puts it on top of a point that's already there.
+    fn add_point(&mut self, i: usize) -> bool {
+        let p = self.coords[i];
+        let key = self.hash_key(p);
+        let mut start = 0;
+        for j in 0..self.hash.len() {
+            start = self.hash[(key + j) % self.hash.len()];
+            if start != EMPTY && start != self.hull_next[start] { break; }
+        }
+
+        // Find the first hull edge that p is to the right of.
+        let start = self.hull_prev[start];
+        let mut e = start;
+        while orientation(self.coords[e], self.coords[self.hull_next[e]], p) >= 0.0 {
+            e = self.hull_next[e];
+            if e == start { return false; }
+        }
+
+        let next = self.hull_next[e];
+        let t = self.add_triangle(e, i, next, EMPTY, EMPTY, self.hull_tri[e]);
+        self.hull_tri[i] = self.legalize(t + 2);
+        self.hull_tri[e] = t;
+
+        // Cover the rest of the hull edges p can see, going forwards and then backwards.
+        let mut n = next;
+        loop {
+            let q = self.hull_next[n];
+            if orientation(self.coords[n], self.coords[q], p) >= 0.0 { break; }
+            let t = self.add_triangle(n, i, q, self.hull_tri[i], EMPTY, self.hull_tri[n]);
+            self.hull_tri[i] = self.legalize(t + 2);
+            self.hull_next[n] = n;
+            n = q;
+        }
+        if e == start {
+            loop {
+                let q = self.hull_prev[e];
+                if orientation(self.coords[q], self.coords[e], p) >= 0.0 { break; }
+                let t = self.add_triangle(q, i, e, EMPTY, self.hull_tri[e], self.hull_tri[q]);
+                self.legalize(t + 2);
+                self.hull_tri[q] = t;
+                self.hull_next[e] = e;
+                e = q;
+            }
+        }
+
+        self.hull_start = e;
+        self.hull_prev[i] = e;
+        self.hull_next[e] = i;
+        self.hull_prev[n] = i;
+        self.hull_next[i] = n;
+        let (key_i, key_e) = (self.hash_key(p), self.hash_key(self.coords[e]));
+        self.hash[key_i] = i;
+        self.hash[key_e] = e;
+        true
+    }
+}
+
+// Returns the edges of a Delaunay triangulation of distinct points, or None if the sweep can't
+// add every point, which can happen when the seed triangle is so flat that the distances from its
+// circumcenter are mostly rounding error. Points all on one line are joined up in order along
+// the line instead.
+fn delaunay_edges(coords: &[(f64, f64)]) -> Option<Vec<(usize, usize)>> {
+    let n = coords.len();
+    let by_position = |&a: &usize, &b: &usize| {
+        coords[a].partial_cmp(&coords[b]).unwrap_or(Ordering::Equal)
+    };
+    if n < 3 {
+        return Some((1..n).map(|i| (0, i)).collect());
+    }
+
+    // Seed with the point nearest the middle, its nearest neighbour, and the point making the
+    // smallest circle with those two.
+    let (min_x, max_x) = coords.iter().fold((f64::INFINITY, f64::NEG_INFINITY),
+                                            |(lo, hi), p| (lo.min(p.0), hi.max(p.0)));
+    let (min_y, max_y) = coords.iter().fold((f64::INFINITY, f64::NEG_INFINITY),
+                                            |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+    let middle = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+    let nearest = |from: (f64, f64), skip: usize| {
+        (0..n).filter(|&i| i != skip)
+            .min_by(|&a, &b| {
+                distance_squared(from, coords[a]).total_cmp(&distance_squared(from, coords[b]))
+            })
+            .unwrap()
+    };
+    let i0 = nearest(middle, EMPTY);
+    let i1 = nearest(coords[i0], i0);
+    let radius = |i: usize| {
+        let r = distance_squared(circumcenter(coords[i0], coords[i1], coords[i]), coords[i0]);
+        if r.is_finite() { r } else { f64::INFINITY }
+    };
+    let i2 = (0..n)
+        .filter(|&i| i != i0 && i != i1 && orientation(coords[i0], coords[i1], coords[i]) != 0.0)
+        .min_by(|&a, &b| radius(a).total_cmp(&radius(b)));
+    let i2 = match i2 {
+        Some(i2) => i2,
+        None => {
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(by_position);
+            return Some(order.windows(2).map(|pair| (pair[0], pair[1])).collect());
+        }
+    };
+
+    let (i1, i2) = if orientation(coords[i0], coords[i1], coords[i2]) < 0.0 {
+        (i2, i1)
+    } else {
+        (i1, i2)
+    };
+    let center = circumcenter(coords[i0], coords[i1], coords[i2]);
+    if !center.0.is_finite() || !center.1.is_finite() { return None; }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        distance_squared(center, coords[a]).total_cmp(&distance_squared(center, coords[b]))
+    });
+
+    let mut triangulation = Triangulation {
+        coords,
+        triangles: Vec::with_capacity(3 * (2 * n - 5)),
+        halfedges: Vec::with_capacity(3 * (2 * n - 5)),
+        hull_next: vec![EMPTY; n],
+        hull_prev: vec![EMPTY; n],
+        hull_tri: vec![EMPTY; n],
+        hull_start: i0,
+        hash: vec![EMPTY; (n as f64).sqrt().ceil() as usize],
+        center,
+    };
+    triangulation.hull_next[i0] = i1;
+    triangulation.hull_next[i1] = i2;
+    triangulation.hull_next[i2] = i0;
+    triangulation.hull_prev[i0] = i2;
+    triangulation.hull_prev[i1] = i0;
+    triangulation.hull_prev[i2] = i1;
+    for (k, &i) in [i0, i1, i2].iter().enumerate() {
+        triangulation.hull_tri[i] = k;
+        let key = triangulation.hash_key(coords[i]);
+        triangulation.hash[key] = i;
+    }
+    triangulation.add_triangle(i0, i1, i2, EMPTY, EMPTY, EMPTY);
+
+    for &i in order.iter() {
+        if i == i0 || i == i1 || i == i2 { continue; }
+        if !triangulation.add_point(i) { return None; }
+    }
+
+    let (triangles, halfedges) = (&triangulation.triangles, &triangulation.halfedges);
+    Some((0..triangles.len())
+        .filter(|&h| halfedges[h] == EMPTY || h < halfedges[h])
+        .map(|h| (triangles[h], triangles[h - h % 3 + (h + 1) % 3]))
+        .collect())
+}
+
+// Prim's algorithm on the complete graph of the points, for when they can't be triangulated --
+// runs in O(n^2).
+fn dense_prims(coords: &[(f64, f64)]) -> Vec<(usize, usize)> {
+    let n = coords.len();
+    let mut in_tree = vec![false; n];
+    let mut nearest: Vec<(f64, usize)> = vec![(f64::INFINITY, EMPTY); n];
+    let mut edges = Vec::with_capacity(n);
+    let mut u = 0;
+    for _ in 1..n {
+        in_tree[u] = true;
+        let mut next = EMPTY;
+        for v in (0..n).filter(|&v| !in_tree[v]) {
+            let d = distance_squared(coords[u], coords[v]);
+            if d < nearest[v].0 {
+                nearest[v] = (d, u);
+            }
+            if next == EMPTY || nearest[v].0 < nearest[next].0 {
+                next = v;
+            }
+        }
+        edges.push((nearest[next].1, next));
+        u = next;
+    }
+    edges
+}
+
+impl EuclideanMinimumSpanningTree for Delaunay {
+    fn euclidean_mst(&self, points: &[Point<f32>]) -> (f32, Vec<(usize, usize)>) {
+        let mut graph: AdjacencyList<(), f32> = AdjacencyList::new();
+        for _ in points.iter() {
+            graph.add_node(());
+        }
+        let add_edge = |graph: &mut AdjacencyList<(), f32>, i: usize, j: usize| {
+            graph.add_edge(i, j, points[i].distance(&points[j]));
+        };
+
+        // Copies of a point hang off its first occurrence, and only the rest are triangulated.
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        order.sort_by(|&a, &b| {
+            (points[a].x, points[a].y).partial_cmp(&(points[b].x, points[b].y))
+                .unwrap_or(Ordering::Equal)
+        });
+        let mut distinct: Vec<usize> = Vec::new();
+        for &i in order.iter() {
+            match distinct.last() {
+                Some(&j) if points[j].x == points[i].x && points[j].y == points[i].y => {
+                    add_edge(&mut graph, j, i);
+                }
+                _ => distinct.push(i),
+            }
+        }
+
+        let coords: Vec<(f64, f64)> = distinct.iter()
+            .map(|&i| (points[i].x as f64, points[i].y as f64))
+            .collect();
+        let edges = delaunay_edges(&coords).unwrap_or_else(|| dense_prims(&coords));
+        for (u, v) in edges.into_iter() {
+            add_edge(&mut graph, distinct[u], distinct[v]);
+        }
+
+        Kruskals.minimum_spanning_tree(&graph).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::*;
+    use point::Point;
+
+    // The MST of the complete graph on the points.
+    fn brute_force(points: &[Point<f32>]) -> f32 {
+        let mut graph: AdjacencyList<(), f32> = AdjacencyList::new();
+        for _ in points.iter() { graph.add_node(()); }
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                graph.add_edge(i, j, points[i].distance(&points[j]));
+            }
+        }
+        Kruskals.minimum_spanning_tree(&graph).unwrap().0
+    }
+
+    #[test]
+    fn simple() {
+        let points = [
+            Point { x: 0.0f32, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 4.0, y: 3.0 },
+            Point { x: 0.0, y: 3.0 },
+            Point { x: 2.0, y: 1.5 },
+        ];
+
+        let (total, mut tree) = Delaunay.euclidean_mst(&points);
+        assert!((total - 10.0).abs() < 1e-5);
+        for edge in tree.iter_mut() {
+            *edge = (::std::cmp::max(edge.0, edge.1), ::std::cmp::min(edge.0, edge.1));
+        }
+        tree.sort();
+        assert_eq!(tree, vec![(4, 0), (4, 1), (4, 2), (4, 3)]);
+
+        assert_eq!(Delaunay.euclidean_mst(&[]), (0.0, vec![]));
+        assert_eq!(Delaunay.euclidean_mst(&points[..1]), (0.0, vec![]));
+    }
+
+    #[test]
+    fn degenerate() {
+        // Points on a line, repeated points, and a grid full of cocircular points.
+        let line: Vec<_> = [3.0f32, -1.0, 7.5, 0.0, 2.0].iter()
+            .map(|&t| Point { x: t, y: 2.0 * t })
+            .collect();
+        let (total, tree) = Delaunay.euclidean_mst(&line);
+        assert!((total - 8.5 * 5.0f32.sqrt()).abs() < 1e-4);
+        assert_eq!(tree.len(), 4);
+
+        let mut repeated = line.clone();
+        repeated.extend(line.iter().cloned());
+        repeated.push(Point { x: 1.0, y: 1.0 });
+        let (total, tree) = Delaunay.euclidean_mst(&repeated);
+        assert!((total - brute_force(&repeated)).abs() < 1e-4);
+        assert_eq!(tree.len(), repeated.len() - 1);
+
+        let grid: Vec<_> = (0..100).map(|i| Point { x: (i % 10) as f32, y: (i / 10) as f32 })
+            .collect();
+        let (total, tree) = Delaunay.euclidean_mst(&grid);
+        assert!((total - 99.0).abs() < 1e-4);
+        assert_eq!(tree.len(), 99);
+    }
+
+    #[test]
+    fn nearly_collinear() {
+        // Rounded determinants get the orientations of these points wrong, which used to give
+        // trees far too long. The second set is flat enough that it isn't triangulated at all.
+        let sets = [
+            vec![(805481.0f32, 0.00058), (946690.0, 0.00045), (263961.0, 0.0007),
+                 (657847.0, 0.00036), (406647.0, 0.00005), (190789.0, 0.00072)],
+            vec![(148394.58, 0.0008549631), (388853.3, 0.00040683892), (149065.25, 0.00054119626),
+                 (629380.0, 0.0009399374), (988848.56, 0.00038149086), (29466.271, 0.00030019166)],
+        ];
+        for set in sets.iter() {
+            let points: Vec<_> = set.iter().map(|&(x, y)| Point { x, y }).collect();
+            let (total, tree) = Delaunay.euclidean_mst(&points);
+            assert_eq!(tree.len(), 5);
+            assert!((total - brute_force(&points)).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn random() {
+        for _ in 0..20 {
+            let points: Vec<_> = (0..200)
+                .map(|_| Point { x: rand::random::<f32>(), y: rand::random::<f32>() })
+                .map(|p| Point { x: p.x * 100.0, y: p.y * 100.0 })
+                .collect();
+            let (total, tree) = Delaunay.euclidean_mst(&points);
+            assert_eq!(tree.len(), points.len() - 1);
+
+            let length: f32 = tree.iter().map(|&(i, j)| points[i].distance(&points[j])).sum();
+            assert!((total - length).abs() < 1e-2);
+            assert!((total - brute_force(&points)).abs() < 1e-2);
+        }
+    }
+}
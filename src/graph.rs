@@ -3,6 +3,8 @@
 use std::collections::HashMap;
 use std::cmp::Ordering;
 
+use weight::Weight;
+
 pub trait Graph {
     type NodeValue;
     type EdgeValue;
@@ -15,16 +17,16 @@ pub trait Graph {
     fn edges(&self) -> Vec<(Self::NodeIndex, Self::NodeIndex, &Self::EdgeValue)>;
 
     /// Adds a new node to the graph and returns its index.
-    fn add_node(&mut self, Self::NodeValue) -> Self::NodeIndex;
+    fn add_node(&mut self, value: Self::NodeValue) -> Self::NodeIndex;
 
     /// Adds a new edge to the graph.
-    fn add_edge(&mut self, Self::NodeIndex, Self::NodeIndex, Self::EdgeValue);
+    fn add_edge(&mut self, from: Self::NodeIndex, to: Self::NodeIndex, weight: Self::EdgeValue);
 
     /// Returns a map of the out-neighbors of a node to the weights of the edges from the node to the out-neighbors.
-    fn neighbors(&self, Self::NodeIndex) -> Vec<Self::NodeIndex>;
+    fn neighbors(&self, node: Self::NodeIndex) -> Vec<Self::NodeIndex>;
 
     /// Returns list of edges on an arbitrary path from source to sink.
-    fn find_path(&self, Self::NodeIndex, Self::NodeIndex) ->
+    fn find_path(&self, source: Self::NodeIndex, sink: Self::NodeIndex) ->
         Option<Vec<(Self::NodeIndex, Self::NodeIndex)>>;
 }
 
@@ -51,14 +53,13 @@ impl<N, E> AdjacencyList<N, E> {
         if source == sink { Some(path) }
         else {
             for neighbor in self.neighbors(source).into_iter() {
-                let edge = (source.clone(), neighbor.clone());
+                let edge = (source, neighbor);
                 if path.contains(&edge) { continue; }
                 let mut new_path = path.clone();
                 new_path.push(edge);
 
-                match self.find_path_helper(neighbor, sink, new_path) {
-                    Some(p) => { return Some(p) },
-                    None => {}
+                if let Some(p) = self.find_path_helper(neighbor, sink, new_path) {
+                    return Some(p);
                 }
             }
 
@@ -67,13 +68,19 @@ impl<N, E> AdjacencyList<N, E> {
     }
 }
 
+impl<N, E> Default for AdjacencyList<N, E> {
+    fn default() -> Self {
+        AdjacencyList::new()
+    }
+}
+
 impl<N, E> Graph for AdjacencyList<N, E> {
     type NodeValue = N;
     type EdgeValue = E;
     type NodeIndex = usize;
 
     fn nodes(&self) -> Vec<NodeIndex> {
-        self.nodes.keys().map(|x| *x).collect()
+        self.nodes.keys().cloned().collect()
     }
 
     fn edges(&self) -> Vec<(NodeIndex, NodeIndex, &E)> {
@@ -88,21 +95,16 @@ impl<N, E> Graph for AdjacencyList<N, E> {
         let index = self.counter;
         self.nodes.insert(index, value);
         self.counter += 1;
-        return index;
+        index
     }
 
     fn add_edge(&mut self, from: NodeIndex, to: NodeIndex, weight: E) {
-        if !self.edges.contains_key(&from) {
-            self.edges.insert(from, HashMap::new());
-        }
-
-        let mut list = self.edges.get_mut(&from).unwrap();
-        list.insert(to, weight);
+        self.edges.entry(from).or_default().insert(to, weight);
     }
 
     fn neighbors(&self, node: NodeIndex) -> Vec<NodeIndex> {
         match self.edges.get(&node) {
-            Some(map) => map.keys().map(|x| *x).collect(),
+            Some(map) => map.keys().cloned().collect(),
             None => panic!("node {} does not exist", node)
         }
     }
@@ -115,23 +117,25 @@ impl<N, E> Graph for AdjacencyList<N, E> {
 // HeapEdge is used for creating a min-heap over edges of the Graph
 // in conjunction with std::collections::BinaryHeap
 
-#[derive(PartialEq, Eq)]
-pub struct HeapEdge<G: Graph>(pub (G::NodeIndex, G::NodeIndex), pub G::EdgeValue) where G::EdgeValue: Eq, G::NodeIndex: Eq;
+pub struct HeapEdge<G: Graph>(pub (G::NodeIndex, G::NodeIndex), pub G::EdgeValue);
 
-impl<G: Graph> Ord for HeapEdge<G> where G: Eq, G::NodeIndex: Eq, G::EdgeValue: Ord {
+impl<G: Graph> PartialEq for HeapEdge<G> where G::EdgeValue: Weight {
+    fn eq(&self, other: &HeapEdge<G>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<G: Graph> Eq for HeapEdge<G> where G::EdgeValue: Weight {}
+
+impl<G: Graph> Ord for HeapEdge<G> where G::EdgeValue: Weight {
     fn cmp(&self, other: &HeapEdge<G>) -> Ordering {
-        use std::cmp::Ordering::{Less, Greater, Equal};
-        let (&HeapEdge(_, ref me), &HeapEdge(_, ref other)) = (self, other);
-        match me.cmp(other) {
-            Less => Greater,
-            Equal => Equal,
-            Greater => Less
-        }
+        let (HeapEdge(_, me), HeapEdge(_, other)) = (self, other);
+        other.compare(me)
     }
 }
 
-impl<G: Graph> PartialOrd for HeapEdge<G> where G: Eq, G::NodeIndex: Eq, G::EdgeValue: Ord {
+impl<G: Graph> PartialOrd for HeapEdge<G> where G::EdgeValue: Weight {
     fn partial_cmp(&self, other: &HeapEdge<G>) -> Option<Ordering> {
         Some(self.cmp(other))
     }
-}
\ No newline at end of file
+}
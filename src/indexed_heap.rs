@@ -0,0 +1,203 @@
+//! Implements an indexed [d-ary heap](http://en.wikipedia.org/wiki/D-ary_heap): a min-priority
+//! queue over the items 0 to n - 1 whose keys can be looked up and decreased in place.
+
+use std::cmp::Ordering;
+
+use weight::Weight;
+
+/// Min-heap of items from 0 to n - 1, each in the heap at most once, keyed by weights. Pushing,
+/// popping and changing a key run in O(d log_d n) for a heap with d children per node, except that
+/// decreasing a key only takes O(log_d n). Algorithms that decrease keys much more often than they
+/// pop, like Prim's or Dijkstra's on dense graphs, can pick a larger d to make that cheaper.
+pub struct IndexedHeap<K> {
+    arity: usize,
+    heap: Vec<(usize, K)>,
+    positions: Vec<Option<usize>>,
+}
+
+impl<K: Weight> IndexedHeap<K> {
+    /// Creates an empty binary heap for the items 0 to n - 1.
+    pub fn new(n: usize) -> Self {
+        IndexedHeap::with_arity(n, 2)
+    }
+
+    /// Creates an empty heap for the items 0 to n - 1 where each node has up to `arity` children.
+    pub fn with_arity(n: usize, arity: usize) -> Self {
+        if arity < 2 {
+            panic!("heap nodes need at least 2 children, got {}", arity);
+        }
+
+        IndexedHeap {
+            arity,
+            heap: Vec::new(),
+            positions: vec![None; n],
+        }
+    }
+
+    /// Returns the number of items in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns whether the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns whether the item is in the heap.
+    pub fn contains(&self, item: usize) -> bool {
+        self.positions[item].is_some()
+    }
+
+    /// Returns the key of the item, or None if it isn't in the heap.
+    pub fn key(&self, item: usize) -> Option<K> {
+        self.positions[item].map(|i| self.heap[i].1)
+    }
+
+    /// Returns the item with the smallest key without removing it.
+    pub fn peek(&self) -> Option<(usize, K)> {
+        self.heap.first().cloned()
+    }
+
+    /// Adds the item with the given key, or changes its key if it's already in the heap.
+    pub fn push(&mut self, item: usize, key: K) {
+        match self.positions[item] {
+            Some(i) => {
+                let old = self.heap[i].1;
+                self.heap[i].1 = key;
+                if key.compare(&old) == Ordering::Less { self.sift_up(i); } else { self.sift_down(i); }
+            }
+            None => {
+                self.heap.push((item, key));
+                let i = self.heap.len() - 1;
+                self.positions[item] = Some(i);
+                self.sift_up(i);
+            }
+        }
+    }
+
+    /// Adds the item if it isn't in the heap, or lowers its key if the new one is smaller.
+    /// Returns whether anything changed.
+    pub fn decrease_key(&mut self, item: usize, key: K) -> bool {
+        match self.key(item) {
+            Some(old) if key.compare(&old) != Ordering::Less => false,
+            _ => {
+                self.push(item, key);
+                true
+            }
+        }
+    }
+
+    /// Removes and returns the item with the smallest key.
+    pub fn pop(&mut self) -> Option<(usize, K)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (item, key) = self.heap.pop().unwrap();
+        self.positions[item] = None;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((item, key))
+    }
+
+    /// Removes the item from the heap if it's there, returning its key.
+    pub fn remove(&mut self, item: usize) -> Option<K> {
+        let i = self.positions[item]?;
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let (_, key) = self.heap.pop().unwrap();
+        self.positions[item] = None;
+        if i < self.heap.len() {
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        Some(key)
+    }
+
+    fn less(&self, i: usize, j: usize) -> bool {
+        self.heap[i].1.compare(&self.heap[j].1) == Ordering::Less
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.positions[self.heap[i].0] = Some(i);
+        self.positions[self.heap[j].0] = Some(j);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / self.arity;
+            if !self.less(i, parent) { break; }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let first = i * self.arity + 1;
+            let last = ::std::cmp::min(first + self.arity, self.heap.len());
+            let smallest = (first..last).fold(i, |best, child| {
+                if self.less(child, best) { child } else { best }
+            });
+            if smallest == i { break; }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let mut heap = IndexedHeap::new(5);
+        heap.push(0, 5u32);
+        heap.push(1, 3);
+        heap.push(2, 8);
+        heap.push(3, 1);
+        assert_eq!(heap.peek(), Some((3, 1)));
+
+        assert!(heap.decrease_key(2, 0));
+        assert!(!heap.decrease_key(1, 4));
+        assert!(heap.decrease_key(4, 2));
+        assert_eq!(heap.key(1), Some(3));
+        assert_eq!(heap.remove(0), Some(5));
+        assert!(!heap.contains(0));
+
+        let order: Vec<_> = (0..4).map(|_| heap.pop().unwrap()).collect();
+        assert_eq!(order, vec![(2, 0), (3, 1), (4, 2), (1, 3)]);
+        assert!(heap.is_empty() && heap.pop().is_none());
+    }
+
+    #[test]
+    fn random() {
+        for &arity in [2, 3, 8].iter() {
+            let n = 200;
+            let mut heap = IndexedHeap::with_arity(n, arity);
+            let mut keys: Vec<Option<u32>> = vec![None; n];
+            for _ in 0..2000 {
+                let item = rand::random::<usize>() % n;
+                let key = rand::random::<u32>() % 1000;
+                heap.push(item, key);
+                keys[item] = Some(key);
+            }
+
+            let mut previous = 0;
+            while let Some((item, key)) = heap.pop() {
+                assert!(key >= previous);
+                assert_eq!(keys[item].take(), Some(key));
+                previous = key;
+            }
+            assert!(keys.iter().all(|key| key.is_none()));
+        }
+    }
+}
@@ -1,10 +1,22 @@
 #![allow(dead_code)]
 
+#[allow(clippy::needless_lifetimes)]
 pub mod kth_largest;
 
 pub mod graph;
+pub mod weight;
 pub mod mst;
+pub mod arborescence;
+pub mod steiner;
+pub mod union_find;
+pub mod indexed_heap;
 pub mod max_flow;
+pub mod matching;
+pub mod min_cut;
 
+#[allow(unused_must_use, clippy::needless_lifetimes)]
 pub mod point;
-pub mod closest_pair;
\ No newline at end of file
+#[allow(clippy::redundant_field_names, clippy::single_match, clippy::explicit_auto_deref,
+        clippy::flat_map_identity, clippy::needless_range_loop)]
+pub mod closest_pair;
+pub mod euclidean_mst;
\ No newline at end of file
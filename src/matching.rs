@@ -0,0 +1,584 @@
+//! Implements [matching algorithms](http://en.wikipedia.org/wiki/Matching_(graph_theory)) on graphs.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use graph::Graph;
+use weight::{self, Weight};
+
+/// Given a graph G and a partition of its nodes into L and R, returns the pairs (l, r) in a
+/// maximum matching of G along with a minimum vertex cover of G.
+///
+/// Edges are treated as undirected, and edges that don't go between L and R are ignored. By
+/// [König's theorem](http://en.wikipedia.org/wiki/K%C5%91nig%27s_theorem_(graph_theory)) the
+/// cover has exactly one node per matched pair.
+pub trait BipartiteMatching<G: Graph> {
+    #[allow(clippy::type_complexity)]
+    fn maximum_matching(&self, graph: &G, left: &[G::NodeIndex], right: &[G::NodeIndex])
+                        -> (Vec<(G::NodeIndex, G::NodeIndex)>, Vec<G::NodeIndex>);
+}
+
+/// [Hopcroft-Karp](http://en.wikipedia.org/wiki/Hopcroft%E2%80%93Karp_algorithm) -- runs in
+/// O(E sqrt(V)) by augmenting along a maximal set of shortest disjoint paths in each phase.
+pub struct HopcroftKarp;
+
+// Matching state over dense indices, with left nodes 0..L and right nodes 0..R.
+struct Matcher {
+    adjacent: Vec<Vec<usize>>,
+    left_mate: Vec<Option<usize>>,
+    right_mate: Vec<Option<usize>>,
+    distances: Vec<Option<usize>>,
+    // layer of the left nodes that end the shortest augmenting paths in this phase
+    limit: Option<usize>,
+}
+
+impl Matcher {
+    // Layers the left nodes by alternating-path distance from a free left node. Returns whether
+    // some augmenting path exists.
+    fn layer(&mut self) -> bool {
+        let mut queue = VecDeque::new();
+        for l in 0..self.adjacent.len() {
+            self.distances[l] = if self.left_mate[l].is_none() { Some(0) } else { None };
+            if self.left_mate[l].is_none() { queue.push_back(l); }
+        }
+
+        self.limit = None;
+        while let Some(l) = queue.pop_front() {
+            if self.limit.is_some() && self.distances[l] > self.limit { break; }
+            let next = self.distances[l].map(|d| d + 1);
+            for &r in self.adjacent[l].iter() {
+                match self.right_mate[r] {
+                    None => { if self.limit.is_none() { self.limit = self.distances[l]; } }
+                    Some(m) => {
+                        if self.distances[m].is_none() {
+                            self.distances[m] = next;
+                            queue.push_back(m);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.limit.is_some()
+    }
+
+    // Looks for an augmenting path from l that follows the layering, flipping it if found. The
+    // path can be as long as the graph, so the search keeps its own stack of (node, next edge).
+    fn augment(&mut self, l: usize) -> bool {
+        let mut stack = vec![(l, 0)];
+        while let Some(&(l, i)) = stack.last() {
+            if i == self.adjacent[l].len() {
+                // Dead end, so skip l for the rest of this phase.
+                self.distances[l] = None;
+                stack.pop();
+                continue;
+            }
+
+            stack.last_mut().unwrap().1 += 1;
+            let r = self.adjacent[l][i];
+            match self.right_mate[r] {
+                None => if self.distances[l] == self.limit {
+                    for &(l, next) in stack.iter() {
+                        let r = self.adjacent[l][next - 1];
+                        self.left_mate[l] = Some(r);
+                        self.right_mate[r] = Some(l);
+                    }
+                    return true;
+                },
+                Some(m) => if self.distances[m] == self.distances[l].map(|d| d + 1) {
+                    stack.push((m, 0));
+                },
+            }
+        }
+
+        false
+    }
+
+    // König's construction: nodes reachable from free left nodes by alternating paths. The cover
+    // is the unreachable left nodes plus the reachable right nodes.
+    fn cover(&self) -> (Vec<bool>, Vec<bool>) {
+        let mut left_seen = vec![false; self.adjacent.len()];
+        let mut right_seen = vec![false; self.right_mate.len()];
+        let mut stack: Vec<usize> = (0..self.adjacent.len())
+            .filter(|&l| self.left_mate[l].is_none())
+            .collect();
+        for &l in stack.iter() { left_seen[l] = true; }
+
+        while let Some(l) = stack.pop() {
+            for &r in self.adjacent[l].iter() {
+                if right_seen[r] || self.left_mate[l] == Some(r) { continue; }
+                right_seen[r] = true;
+                if let Some(m) = self.right_mate[r] {
+                    if !left_seen[m] {
+                        left_seen[m] = true;
+                        stack.push(m);
+                    }
+                }
+            }
+        }
+
+        (left_seen.into_iter().map(|seen| !seen).collect(), right_seen)
+    }
+}
+
+impl<G: Graph> BipartiteMatching<G> for HopcroftKarp where G::NodeIndex: Hash + Eq {
+    fn maximum_matching(&self, graph: &G, left: &[G::NodeIndex], right: &[G::NodeIndex])
+                        -> (Vec<(G::NodeIndex, G::NodeIndex)>, Vec<G::NodeIndex>)
+    {
+        let left_index: HashMap<_, _> = left.iter().enumerate().map(|(i, &l)| (l, i)).collect();
+        let right_index: HashMap<_, _> = right.iter().enumerate().map(|(i, &r)| (r, i)).collect();
+
+        let mut matcher = Matcher {
+            adjacent: vec![Vec::new(); left.len()],
+            left_mate: vec![None; left.len()],
+            right_mate: vec![None; right.len()],
+            distances: vec![None; left.len()],
+            limit: None,
+        };
+
+        for (from, to, _) in graph.edges().into_iter() {
+            let edge = match (left_index.get(&from), right_index.get(&to)) {
+                (Some(&l), Some(&r)) => Some((l, r)),
+                _ => match (left_index.get(&to), right_index.get(&from)) {
+                    (Some(&l), Some(&r)) => Some((l, r)),
+                    _ => None,
+                },
+            };
+
+            if let Some((l, r)) = edge {
+                matcher.adjacent[l].push(r);
+            }
+        }
+        for adjacent in matcher.adjacent.iter_mut() {
+            adjacent.sort_unstable();
+            adjacent.dedup();
+        }
+
+        while matcher.layer() {
+            for l in 0..left.len() {
+                if matcher.left_mate[l].is_none() { matcher.augment(l); }
+            }
+        }
+
+        let pairs = (0..left.len())
+            .filter_map(|l| matcher.left_mate[l].map(|r| (left[l], right[r])))
+            .collect();
+
+        let (left_cover, right_cover) = matcher.cover();
+        let cover = (0..left.len()).filter(|&l| left_cover[l]).map(|l| left[l])
+            .chain((0..right.len()).filter(|&r| right_cover[r]).map(|r| right[r]))
+            .collect();
+
+        (pairs, cover)
+    }
+}
+
+/// Whether an assignment should have the smallest or the largest total weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    Minimize,
+    Maximize,
+}
+
+/// An optimal assignment together with the dual solution that certifies it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment<N, E> {
+    /// Assigned (row, column) pairs. Every row or every column is assigned, whichever is fewer.
+    pub pairs: Vec<(N, N)>,
+    /// Total weight of the assigned pairs.
+    pub total: E,
+    /// Row potentials u and column potentials v, kept as offsets so that they never go negative
+    /// when the weights don't. When minimizing, u[i] <= w(i, j) + v[j] for every pair, and when
+    /// maximizing, w(i, j) + u[i] <= v[j]. Either way assigned pairs are tight, and rows or
+    /// columns that are left over have the largest u or the smallest v.
+    pub row_potentials: Vec<E>,
+    pub column_potentials: Vec<E>,
+}
+
+/// Given a matrix of weights where w[i][j] is the weight of assigning row i to column j, returns
+/// the assignment with optimal total weight. Rows and columns are matched one-to-one, so if the
+/// matrix isn't square some rows or columns are left over.
+pub trait LinearAssignment<E> {
+    fn assign(&self, weights: &[Vec<E>], objective: Objective) -> Assignment<usize, E>;
+}
+
+/// Given a graph G with weights on the edges and a partition of its nodes into L and R, returns
+/// the optimal assignment between L and R using only edges of G, with potentials indexed like L
+/// and R. Returns None if no assignment covers every node on the smaller side.
+pub trait WeightedBipartiteMatching<G: Graph> {
+    fn assign(&self, graph: &G, left: &[G::NodeIndex], right: &[G::NodeIndex],
+              objective: Objective) -> Option<Assignment<G::NodeIndex, G::EdgeValue>>;
+}
+
+/// [Hungarian algorithm](http://en.wikipedia.org/wiki/Hungarian_algorithm) (Kuhn-Munkres) --
+/// runs in O(n^2 m) for an n x m matrix with n <= m, so O(n^3) when square.
+pub struct Hungarian;
+
+// Solves the minimization problem for n <= m, where None marks a forbidden pair. Returns the
+// column assigned to each row and the row and column potentials, or None if some row can't be
+// assigned. The potentials are kept as offsets with u[i] <= w(i, j) + v[j], which start at zero
+// and only ever grow, so they stay non-negative and unsigned weights work too.
+fn hungarian<E>(weights: &[Vec<Option<E>>], m: usize) -> Option<(Vec<usize>, Vec<E>, Vec<E>)>
+    where E: Weight
+{
+    let zero = E::zero();
+    let n = weights.len();
+    // Rows and columns are shifted up by one so that row and column 0 can be a sentinel.
+    let mut u = vec![zero; n + 1];
+    let mut v = vec![zero; m + 1];
+    let mut row_of = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+
+    for i in 1..(n + 1) {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut slack: Vec<Option<E>> = vec![None; m + 1];
+        let mut used = vec![false; m + 1];
+
+        // Grow a tree of tight edges from row i until it reaches an unassigned column.
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let mut delta: Option<E> = None;
+            let mut j1 = 0;
+
+            for j in 1..(m + 1) {
+                if used[j] { continue; }
+                if let Some(w) = weights[i0 - 1][j - 1] {
+                    let reduced = w + v[j] - u[i0];
+                    if slack[j].is_none_or(|s| reduced.less_than(s)) {
+                        slack[j] = Some(reduced);
+                        way[j] = j0;
+                    }
+                }
+                if let Some(s) = slack[j] {
+                    if delta.is_none_or(|d| s.less_than(d)) {
+                        delta = Some(s);
+                        j1 = j;
+                    }
+                }
+            }
+
+            let delta = delta?;
+            for j in 0..(m + 1) {
+                if used[j] {
+                    u[row_of[j]] = u[row_of[j]] + delta;
+                    v[j] = v[j] + delta;
+                } else if let Some(s) = slack[j] {
+                    slack[j] = Some(s - delta);
+                }
+            }
+
+            j0 = j1;
+            if row_of[j0] == 0 { break; }
+        }
+
+        // Flip the alternating path back to the root.
+        while j0 != 0 {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut columns = vec![0; n];
+    for j in 1..(m + 1) {
+        if row_of[j] != 0 { columns[row_of[j] - 1] = j - 1; }
+    }
+
+    Some((columns, u[1..].to_vec(), v[1..].to_vec()))
+}
+
+// Handles orientation and objective for the core solver. Returns (row, column) pairs.
+fn solve<E>(weights: &[Vec<Option<E>>], rows: usize, columns: usize, objective: Objective)
+            -> Option<Assignment<usize, E>>
+    where E: Weight
+{
+    // Maximizing w is minimizing top - w for the largest weight top, which keeps the weights
+    // the solver sees at or above zero when they're all of one sign, without negating anything.
+    let top = weights.iter().flat_map(|row| row.iter()).filter_map(|&w| w)
+        .fold(None, |top: Option<E>, w| Some(top.map_or(w, |top| weight::max(top, w))))
+        .unwrap_or_else(E::zero);
+    let cost = |w: E| if objective == Objective::Maximize { top - w } else { w };
+    let transpose = rows > columns;
+    let (n, m) = if transpose { (columns, rows) } else { (rows, columns) };
+    let matrix: Vec<Vec<Option<E>>> = (0..n)
+        .map(|i| (0..m).map(|j| {
+            let w = if transpose { weights[j][i] } else { weights[i][j] };
+            w.map(&cost)
+        }).collect())
+        .collect();
+
+    let (assigned, u, v) = hungarian(&matrix, m)?;
+    let mut pairs: Vec<(usize, usize)> = assigned.into_iter().enumerate().collect();
+    let (u, mut v) = if transpose {
+        for pair in pairs.iter_mut() {
+            *pair = (pair.1, pair.0);
+        }
+        pairs.sort();
+
+        // The solver's potentials have the roles swapped, with u[j] <= w(i, j) + v[i], which
+        // subtracting both from their largest value turns back around.
+        let largest = u.iter().chain(v.iter()).cloned().fold(E::zero(), weight::max);
+        let flip = |p: Vec<E>| p.into_iter().map(|x| largest - x).collect::<Vec<_>>();
+        (flip(v), flip(u))
+    } else {
+        (u, v)
+    };
+    if objective == Objective::Maximize {
+        for p in v.iter_mut() {
+            *p = *p + top;
+        }
+    }
+
+    let total = pairs.iter().fold(E::zero(), |acc, &(i, j)| acc + weights[i][j].unwrap());
+    Some(Assignment { pairs, total, row_potentials: u, column_potentials: v })
+}
+
+impl<E: Weight> LinearAssignment<E> for Hungarian {
+    fn assign(&self, weights: &[Vec<E>], objective: Objective) -> Assignment<usize, E> {
+        let rows = weights.len();
+        let columns = if rows == 0 { 0 } else { weights[0].len() };
+        let matrix: Vec<Vec<Option<E>>> = weights.iter()
+            .map(|row| {
+                if row.len() != columns {
+                    panic!("rows of the weight matrix have different lengths");
+                }
+                row.iter().map(|&w| Some(w)).collect()
+            })
+            .collect();
+
+        solve(&matrix, rows, columns, objective).unwrap()
+    }
+}
+
+impl<G: Graph> WeightedBipartiteMatching<G> for Hungarian
+    where G::NodeIndex: Hash + Eq,
+          G::EdgeValue: Weight
+{
+    fn assign(&self, graph: &G, left: &[G::NodeIndex], right: &[G::NodeIndex],
+              objective: Objective) -> Option<Assignment<G::NodeIndex, G::EdgeValue>>
+    {
+        let left_index: HashMap<_, _> = left.iter().enumerate().map(|(i, &l)| (l, i)).collect();
+        let right_index: HashMap<_, _> = right.iter().enumerate().map(|(i, &r)| (r, i)).collect();
+
+        // Edges can go either way; if both directions are present, keep the better weight.
+        let mut matrix = vec![vec![None; right.len()]; left.len()];
+        for (from, to, &weight) in graph.edges().into_iter() {
+            let cell = match (left_index.get(&from), right_index.get(&to)) {
+                (Some(&l), Some(&r)) => Some((l, r)),
+                _ => match (left_index.get(&to), right_index.get(&from)) {
+                    (Some(&l), Some(&r)) => Some((l, r)),
+                    _ => None,
+                },
+            };
+
+            if let Some((l, r)) = cell {
+                let better = match (matrix[l][r], objective) {
+                    (None, _) => true,
+                    (Some(old), Objective::Minimize) => weight.less_than(old),
+                    (Some(old), Objective::Maximize) => old.less_than(weight),
+                };
+                if better { matrix[l][r] = Some(weight); }
+            }
+        }
+
+        let assignment = solve(&matrix, left.len(), right.len(), objective)?;
+        Some(Assignment {
+            pairs: assignment.pairs.into_iter().map(|(l, r)| (left[l], right[r])).collect(),
+            total: assignment.total,
+            row_potentials: assignment.row_potentials,
+            column_potentials: assignment.column_potentials,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+
+    #[test]
+    fn simple() {
+        let mut graph: AdjacencyList<(), ()> = AdjacencyList::new();
+        let left: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        let right: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+
+        // Left 0 and 1 only know right 0, so one of them stays unmatched.
+        graph.add_edge(left[0], right[0], ());
+        graph.add_edge(left[1], right[0], ());
+        graph.add_edge(left[2], right[0], ());
+        graph.add_edge(left[2], right[1], ());
+        graph.add_edge(right[2], left[3], ());
+        graph.add_edge(left[3], right[3], ());
+
+        let (pairs, mut cover) = HopcroftKarp.maximum_matching(&graph, &left, &right);
+        assert_eq!(pairs.len(), 3);
+        assert!(pairs.contains(&(left[2], right[1])));
+        assert_eq!(cover.len(), pairs.len());
+
+        cover.sort();
+        for (from, to, _) in graph.edges().into_iter() {
+            assert!(cover.binary_search(&from).is_ok() || cover.binary_search(&to).is_ok());
+        }
+    }
+
+    #[test]
+    fn perfect() {
+        // A 6-cycle between the sides forces augmenting along longer alternating paths.
+        let mut graph: AdjacencyList<(), ()> = AdjacencyList::new();
+        let left: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+        let right: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+        for i in 0..3 {
+            graph.add_edge(left[i], right[i], ());
+            graph.add_edge(left[i], right[(i + 1) % 3], ());
+        }
+
+        let (pairs, cover) = HopcroftKarp.maximum_matching(&graph, &left, &right);
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(cover.len(), 3);
+    }
+
+    #[test]
+    fn long_path() {
+        // Every left node but the last grabs the right node behind it, so the one augmenting path
+        // left runs the whole length of the chain.
+        let n = 100_000;
+        let mut graph: AdjacencyList<(), ()> = AdjacencyList::new();
+        let left: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+        let right: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+        for i in 0..n {
+            if i > 0 { graph.add_edge(left[i], right[i - 1], ()); }
+            graph.add_edge(left[i], right[i], ());
+        }
+
+        let order: Vec<_> = left.iter().cloned().rev().collect();
+        let (pairs, cover) = HopcroftKarp.maximum_matching(&graph, &order, &right);
+        assert_eq!(pairs.len(), n);
+        assert_eq!(cover.len(), n);
+    }
+
+    // Checks that the potentials certify the assignment as optimal.
+    fn check_duals<E>(weights: &[Vec<E>], assignment: &Assignment<usize, E>, objective: Objective)
+        where E: Weight + Ord + ::std::fmt::Debug
+    {
+        let (u, v) = (&assignment.row_potentials, &assignment.column_potentials);
+        for (i, row) in weights.iter().enumerate() {
+            for (j, &w) in row.iter().enumerate() {
+                match objective {
+                    Objective::Minimize => assert!(u[i] <= w + v[j]),
+                    Objective::Maximize => assert!(w + u[i] <= v[j]),
+                }
+            }
+        }
+        for &(i, j) in assignment.pairs.iter() {
+            match objective {
+                Objective::Minimize => assert_eq!(u[i], weights[i][j] + v[j]),
+                Objective::Maximize => assert_eq!(weights[i][j] + u[i], v[j]),
+            }
+        }
+
+        // Whatever is left over can't be swapped in to do better.
+        let (largest, smallest) = (u.iter().max().unwrap(), v.iter().min().unwrap());
+        for i in (0..u.len()).filter(|&i| assignment.pairs.iter().all(|&(r, _)| r != i)) {
+            assert_eq!(&u[i], largest);
+        }
+        for j in (0..v.len()).filter(|&j| assignment.pairs.iter().all(|&(_, c)| c != j)) {
+            assert_eq!(&v[j], smallest);
+        }
+    }
+
+    #[test]
+    fn hungarian() {
+        let weights = vec![
+            vec![4i64, 1, 3],
+            vec![2, 0, 5],
+            vec![3, 2, 2],
+            ];
+
+        let min = LinearAssignment::assign(&Hungarian, &weights, Objective::Minimize);
+        assert_eq!(min.total, 5);
+        assert_eq!(min.pairs, vec![(0, 1), (1, 0), (2, 2)]);
+        check_duals(&weights, &min, Objective::Minimize);
+
+        let max = LinearAssignment::assign(&Hungarian, &weights, Objective::Maximize);
+        assert_eq!(max.total, 11);
+        check_duals(&weights, &max, Objective::Maximize);
+    }
+
+    #[test]
+    fn hungarian_rectangular() {
+        // More rows than columns, so one row is left out.
+        let weights = vec![
+            vec![10i64, 3],
+            vec![1, 8],
+            vec![2, 9],
+            ];
+
+        let min = LinearAssignment::assign(&Hungarian, &weights, Objective::Minimize);
+        assert_eq!(min.total, 4);
+        assert_eq!(min.pairs, vec![(0, 1), (1, 0)]);
+        check_duals(&weights, &min, Objective::Minimize);
+
+        let max = LinearAssignment::assign(&Hungarian, &weights, Objective::Maximize);
+        assert_eq!(max.total, 19);
+        check_duals(&weights, &max, Objective::Maximize);
+    }
+
+    #[test]
+    fn hungarian_unsigned() {
+        // Potentials are offsets, so unsigned weights work for either objective and shape.
+        let weights = vec![
+            vec![7u32, 2, 9, 4],
+            vec![3, 8, 1, 6],
+            vec![5, 5, 5, 0],
+            ];
+        let transposed: Vec<Vec<u32>> = (0..4).map(|j| (0..3).map(|i| weights[i][j]).collect())
+            .collect();
+
+        for &objective in [Objective::Minimize, Objective::Maximize].iter() {
+            let assignment = LinearAssignment::assign(&Hungarian, &weights, objective);
+            check_duals(&weights, &assignment, objective);
+            let other = LinearAssignment::assign(&Hungarian, &transposed, objective);
+            check_duals(&transposed, &other, objective);
+            assert_eq!(assignment.total, other.total);
+        }
+        assert_eq!(LinearAssignment::assign(&Hungarian, &weights, Objective::Minimize).total, 3);
+        assert_eq!(LinearAssignment::assign(&Hungarian, &weights, Objective::Maximize).total, 22);
+
+        let mut graph: AdjacencyList<(), u8> = AdjacencyList::new();
+        let left: Vec<_> = (0..2).map(|_| graph.add_node(())).collect();
+        let right: Vec<_> = (0..2).map(|_| graph.add_node(())).collect();
+        graph.add_edge(left[0], right[0], 1);
+        graph.add_edge(left[0], right[1], 4);
+        graph.add_edge(left[1], right[1], 3);
+        let assignment = WeightedBipartiteMatching::assign(
+            &Hungarian, &graph, &left, &right, Objective::Maximize).unwrap();
+        assert_eq!(assignment.total, 4);
+    }
+
+    #[test]
+    fn hungarian_graph() {
+        let mut graph: AdjacencyList<(), i64> = AdjacencyList::new();
+        let left: Vec<_> = (0..2).map(|_| graph.add_node(())).collect();
+        let right: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+        graph.add_edge(left[0], right[0], 5);
+        graph.add_edge(left[0], right[1], 1);
+        graph.add_edge(right[1], left[1], 2);
+        graph.add_edge(left[1], right[2], 7);
+
+        let assignment = WeightedBipartiteMatching::assign(
+            &Hungarian, &graph, &left, &right, Objective::Minimize).unwrap();
+        assert_eq!(assignment.total, 7);
+        assert_eq!(assignment.pairs, vec![(left[0], right[0]), (left[1], right[1])]);
+
+        // Nothing connects left[1] once its edges are gone.
+        let mut sparse: AdjacencyList<(), i64> = AdjacencyList::new();
+        let left: Vec<_> = (0..2).map(|_| sparse.add_node(())).collect();
+        let right: Vec<_> = (0..2).map(|_| sparse.add_node(())).collect();
+        sparse.add_edge(left[0], right[0], 1);
+        sparse.add_edge(left[0], right[1], 1);
+        assert!(WeightedBipartiteMatching::assign(
+            &Hungarian, &sparse, &left, &right, Objective::Minimize).is_none());
+    }
+}
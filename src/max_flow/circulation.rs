@@ -0,0 +1,206 @@
+//! Implements [circulations with demands](http://en.wikipedia.org/wiki/Circulation_problem),
+//! where edges have lower as well as upper bounds on their flow, by reduction to max flow.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Neg;
+
+use graph::{AdjacencyList, Graph};
+use weight::{self, Weight};
+use super::{Flows, MaxFlow, MinCut};
+
+/// Lower and upper bounds on the flow through an edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds<E> {
+    pub lower: E,
+    pub upper: E,
+}
+
+/// Proof that no feasible circulation exists: a set of nodes whose total demand is more than the
+/// most flow that could ever enter it, which is the upper bounds of the edges coming in minus the
+/// lower bounds of the edges going out.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Infeasible<N, E> {
+    pub nodes: Vec<N>,
+    pub demand: E,
+    pub capacity: E,
+}
+
+/// Given a graph G with flow bounds on the edges and a demand for each node, returns a flow on
+/// every edge that respects the bounds and where each node's inflow minus its outflow equals its
+/// demand. Nodes with negative demand are supplies, and nodes missing from `demands` have none.
+///
+/// Demands may be negative, so E has to be a signed type. Assumes the demands sum to zero and
+/// that no lower bound is above its upper bound.
+pub trait FeasibleCirculation<G, E> where G: Graph<EdgeValue = Bounds<E>> {
+    fn circulation(&self, graph: &G, demands: &HashMap<G::NodeIndex, E>)
+                   -> Result<Flows<G::NodeIndex, E>,
+                             Infeasible<G::NodeIndex, E>>;
+}
+
+/// Any max flow algorithm solves circulations: after sending every lower bound up front, the
+/// leftover demands are met from a super source and super sink, and the circulation is feasible
+/// exactly when the max flow between them saturates every demand.
+impl<G, E, M> FeasibleCirculation<G, E> for M
+    where G: Graph<EdgeValue = Bounds<E>>, G::NodeIndex: Hash + Eq,
+          E: Weight + Neg<Output = E>, M: MaxFlow<AdjacencyList<(), E>>
+{
+    fn circulation(&self, graph: &G, demands: &HashMap<G::NodeIndex, E>)
+                   -> Result<Flows<G::NodeIndex, E>,
+                             Infeasible<G::NodeIndex, E>>
+    {
+        let zero = E::zero();
+        let nodes = graph.nodes();
+        let edges = graph.edges();
+        let n = nodes.len();
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let demand = |u: usize| demands.get(&nodes[u]).cloned().unwrap_or(zero);
+
+        // Leftover demands are compared against the largest finite demand or bound.
+        let scale = (0..n).map(|u| weight::max(demand(u), -demand(u)))
+            .chain(edges.iter().map(|&(_, _, bounds)| bounds.upper))
+            .filter(|w| w.compare(&E::infinity()) == Ordering::Less)
+            .fold(zero, weight::max);
+
+        if !(0..n).fold(zero, |acc, u| acc + demand(u)).is_zero(scale) {
+            panic!("demands of a circulation have to sum to zero");
+        }
+
+        // What each node still needs once every edge carries its lower bound.
+        let mut remaining: Vec<E> = (0..n).map(&demand).collect();
+        let mut network = AdjacencyList::new();
+        for _ in 0..(n + 2) {
+            network.add_node(());
+        }
+        let (source, sink) = (n, n + 1);
+
+        for &(from, to, bounds) in edges.iter() {
+            if bounds.upper.less_than(bounds.lower) {
+                panic!("lower bound of an edge is above its upper bound");
+            }
+            let (u, v) = (index[&from], index[&to]);
+            remaining[u] = remaining[u] + bounds.lower;
+            remaining[v] = remaining[v] - bounds.lower;
+            network.add_edge(u, v, bounds.upper - bounds.lower);
+        }
+
+        let mut needed = zero;
+        for (u, &r) in remaining.iter().enumerate() {
+            if r.is_above_zero(scale) {
+                network.add_edge(u, sink, r);
+                needed = needed + r;
+            } else if r.is_below_zero(scale) {
+                network.add_edge(source, u, -r);
+            }
+        }
+
+        let (value, flows) = self.max_flow(&network, source, sink);
+        if !value.less_than(needed) {
+            let flows: HashMap<_, _> = flows.into_iter().collect();
+            return Ok(edges.iter()
+                .map(|&(from, to, bounds)| {
+                    ((from, to), bounds.lower + flows[&(index[&from], index[&to])])
+                })
+                .collect());
+        }
+
+        // The sink side of a min cut demands more than can reach it.
+        let (_, sink_side, _) = self.min_cut(&network, source, sink);
+        let mut inside = vec![false; n];
+        for &u in sink_side.iter().filter(|&&u| u < n) { inside[u] = true; }
+
+        let mut capacity = zero;
+        for &(from, to, bounds) in edges.iter() {
+            match (inside[index[&from]], inside[index[&to]]) {
+                (false, true) => { capacity = capacity + bounds.upper; }
+                (true, false) => { capacity = capacity - bounds.lower; }
+                _ => {}
+            }
+        }
+
+        Err(Infeasible {
+            nodes: (0..n).filter(|&u| inside[u]).map(|u| nodes[u]).collect(),
+            demand: (0..n).filter(|&u| inside[u]).fold(zero, |acc, u| acc + demand(u)),
+            capacity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+    use max_flow::Dinic;
+
+    #[test]
+    fn feasible() {
+        // Node 0 supplies 3 units and node 3 consumes them, but (1, 2) has to carry at least 2.
+        let edges = [(0, 1, 0, 3), (0, 2, 0, 3), (1, 2, 2, 4), (1, 3, 0, 1), (2, 3, 0, 5)];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..4 {
+            graph.add_node(());
+        }
+        for &(from, to, lower, upper) in edges.iter() {
+            graph.add_edge(from, to, Bounds { lower, upper });
+        }
+        let demands: HashMap<_, _> = vec![(0, -3), (3, 3)].into_iter().collect();
+
+        let flows: HashMap<_, _> = Dinic.circulation(&graph, &demands).unwrap().into_iter().collect();
+        for &(from, to, lower, upper) in edges.iter() {
+            assert!(lower <= flows[&(from, to)] && flows[&(from, to)] <= upper);
+        }
+        for u in 0..4 {
+            let inflow: i64 = flows.iter().filter(|&(&(_, to), _)| to == u).map(|(_, &f)| f).sum();
+            let outflow: i64 = flows.iter().filter(|&(&(from, _), _)| from == u).map(|(_, &f)| f).sum();
+            assert_eq!(inflow - outflow, demands.get(&u).cloned().unwrap_or(0));
+        }
+    }
+
+    #[test]
+    fn infeasible() {
+        // Node 2 needs 4 units but only 3 can get in.
+        let edges = [(0, 1, 0, 10), (1, 2, 0, 3), (2, 0, 0, 10)];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..3 {
+            graph.add_node(());
+        }
+        for &(from, to, lower, upper) in edges.iter() {
+            graph.add_edge(from, to, Bounds { lower, upper });
+        }
+        let demands: HashMap<_, _> = vec![(0, -4), (2, 4)].into_iter().collect();
+
+        let certificate = Dinic.circulation(&graph, &demands).unwrap_err();
+        assert_eq!(certificate.nodes, vec![2]);
+        assert_eq!(certificate.demand, 4);
+        assert_eq!(certificate.capacity, 3);
+    }
+
+    #[test]
+    fn lower_bounds() {
+        // A cycle with no demands still has to carry the largest lower bound around it.
+        let edges = [(0, 1, 2, 5), (1, 2, 0, 5), (2, 0, 4, 5)];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..3 {
+            graph.add_node(());
+        }
+        for &(from, to, lower, upper) in edges.iter() {
+            graph.add_edge(from, to, Bounds { lower, upper });
+        }
+        let flows = Dinic.circulation(&graph, &HashMap::new()).unwrap();
+        assert!(flows.iter().all(|&(_, f)| f >= 4));
+
+        let edges = [(0, 1, 3, 5), (1, 0, 0, 2)];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..2 {
+            graph.add_node(());
+        }
+        for &(from, to, lower, upper) in edges.iter() {
+            graph.add_edge(from, to, Bounds { lower, upper });
+        }
+        let certificate = Dinic.circulation(&graph, &HashMap::new()).unwrap_err();
+        assert!(certificate.demand > certificate.capacity);
+    }
+}
@@ -0,0 +1,207 @@
+//! Implements [flow decomposition](http://en.wikipedia.org/wiki/Flow_network#Flow_decomposition)
+//! of per-edge flows into weighted paths and cycles.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use weight::{self, Weight};
+
+// Remaining flow on each edge, with a pointer per node past the out-edges already used up. Flow
+// within the tolerance of the largest flow counts as used up.
+struct Remaining<E> {
+    adjacent: Vec<Vec<usize>>,
+    heads: Vec<usize>,
+    flows: Vec<E>,
+    next: Vec<usize>,
+    scale: E,
+}
+
+impl<E: Weight> Remaining<E> {
+    fn out_edge(&mut self, u: usize) -> Option<usize> {
+        while self.next[u] < self.adjacent[u].len() {
+            let edge = self.adjacent[u][self.next[u]];
+            if self.flows[edge].is_above_zero(self.scale) { return Some(edge); }
+            self.next[u] += 1;
+        }
+        None
+    }
+
+    // Subtracts the smallest flow on the given edges, but no more than `limit`, from all of them
+    // and returns it.
+    fn take(&mut self, edges: &[usize], limit: Option<E>) -> E {
+        let smallest = edges.iter().map(|&edge| self.flows[edge]).reduce(weight::min).unwrap();
+        let amount = limit.map_or(smallest, |limit| weight::min(smallest, limit));
+        for &edge in edges.iter() {
+            self.flows[edge] = self.flows[edge] - amount;
+        }
+        amount
+    }
+
+    // Follows flow out of `start` until it reaches `stop`, peeling off every cycle the walk
+    // closes along the way. Returns the path if `stop` was reached, or None once `start` has no
+    // flow left to follow.
+    fn walk(&mut self, start: usize, stop: Option<usize>,
+            cycles: &mut Vec<(Vec<usize>, E)>) -> Option<(Vec<usize>, Vec<usize>)> {
+        let mut nodes = vec![start];
+        let mut edges: Vec<usize> = Vec::new();
+        let mut position: HashMap<usize, usize> = HashMap::new();
+        position.insert(start, 0);
+
+        loop {
+            let u = *nodes.last().unwrap();
+            if Some(u) == stop { return Some((nodes, edges)); }
+
+            let edge = match self.out_edge(u) {
+                Some(edge) => edge,
+                None => { return None; }
+            };
+            let v = self.heads[edge];
+
+            match position.get(&v).cloned() {
+                Some(i) => {
+                    let mut cycle_edges = edges.split_off(i);
+                    cycle_edges.push(edge);
+                    let amount = self.take(&cycle_edges, None);
+                    for &w in nodes[(i + 1)..].iter() { position.remove(&w); }
+                    let cycle_nodes = nodes.split_off(i);
+                    cycles.push((cycle_nodes, amount));
+                    // Edges before the cycle still carry flow, so keep walking from v.
+                    nodes.push(v);
+                }
+                None => {
+                    position.insert(v, nodes.len());
+                    nodes.push(v);
+                    edges.push(edge);
+                }
+            }
+        }
+    }
+}
+
+/// Given the flow on each edge of a network, such as the output of a `MaxFlow`, along with its
+/// source S and sink T, splits it into weighted S-T paths plus weighted cycles whose sum is the
+/// original flow, with the paths carrying exactly the net flow out of S. Paths and cycles are
+/// given as sequences of nodes; a cycle's first node is not repeated at the end.
+///
+/// Every path or cycle but the last path uses up the remaining flow on at least one edge, so
+/// there are at most E + 1 of them in total. Runs in O(VE). Assumes the flow is conserved at
+/// every node other than S and T.
+#[allow(clippy::type_complexity)]
+pub fn decompose<N, E>(flows: &[((N, N), E)], source: N, sink: N)
+                       -> (Vec<(Vec<N>, E)>, Vec<(Vec<N>, E)>)
+    where N: Copy + Hash + Eq, E: Weight
+{
+    let mut nodes: Vec<N> = Vec::new();
+    let mut index: HashMap<N, usize> = HashMap::new();
+    let mut node = |n: N| -> usize {
+        *index.entry(n).or_insert_with(|| { nodes.push(n); nodes.len() - 1 })
+    };
+
+    let (s, t) = (node(source), node(sink));
+    let edges: Vec<(usize, usize)> = flows.iter().map(|&((u, v), _)| (node(u), node(v))).collect();
+    let n = nodes.len();
+
+    let mut remaining = Remaining {
+        adjacent: vec![Vec::new(); n],
+        heads: edges.iter().map(|&(_, v)| v).collect(),
+        flows: flows.iter().map(|&(_, flow)| flow).collect(),
+        next: vec![0; n],
+        scale: flows.iter().map(|&(_, flow)| flow).fold(E::zero(), weight::max),
+    };
+    for (k, &(u, _)) in edges.iter().enumerate() {
+        remaining.adjacent[u].push(k);
+    }
+
+    let mut paths = Vec::new();
+    let mut cycles = Vec::new();
+    if s != t {
+        // Only the net flow out of S goes on paths; a cycle through both S and T stays a cycle.
+        let (mut outflow, mut inflow) = (E::zero(), E::zero());
+        for (&(u, v), &flow) in edges.iter().zip(remaining.flows.iter()) {
+            if u == v { continue; }
+            if u == s { outflow = outflow + flow; }
+            if v == s { inflow = inflow + flow; }
+        }
+        let mut value = if inflow.less_than(outflow) { outflow - inflow } else { E::zero() };
+
+        while value.is_above_zero(remaining.scale) {
+            let (path_nodes, path_edges) = match remaining.walk(s, Some(t), &mut cycles) {
+                Some(path) => path,
+                None => { break; }
+            };
+            let amount = remaining.take(&path_edges, Some(value));
+            value = value - amount;
+            paths.push((path_nodes, amount));
+        }
+    }
+
+    // Whatever is left is conserved everywhere, so it all lies on cycles.
+    for u in 0..n {
+        remaining.walk(u, None, &mut cycles);
+    }
+
+    let to_nodes = |list: Vec<(Vec<usize>, E)>| -> Vec<(Vec<N>, E)> {
+        list.into_iter()
+            .map(|(path, amount)| (path.into_iter().map(|u| nodes[u]).collect(), amount))
+            .collect()
+    };
+    (to_nodes(paths), to_nodes(cycles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+    use max_flow::{MaxFlow, Dinic};
+
+    #[test]
+    fn simple() {
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 3u32);
+        graph.add_edge(v[0], v[2], 2);
+        graph.add_edge(v[1], v[2], 1);
+        graph.add_edge(v[1], v[3], 2);
+        graph.add_edge(v[2], v[3], 3);
+
+        let (value, flows) = Dinic.max_flow(&graph, v[0], v[3]);
+        let (paths, cycles) = decompose(&flows, v[0], v[3]);
+        assert!(cycles.is_empty());
+        assert!(paths.len() <= flows.len());
+        assert_eq!(paths.iter().fold(0, |acc, &(_, amount)| acc + amount), value);
+        for (path, _) in paths.iter() {
+            assert_eq!(path[0], v[0]);
+            assert_eq!(*path.last().unwrap(), v[3]);
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        // One unit from 0 to 3 plus a circulation of 2 around 1 -> 2 -> 4 -> 1.
+        let flows = vec![
+            ((0, 1), 1u32),
+            ((1, 2), 3),
+            ((2, 3), 1),
+            ((2, 4), 2),
+            ((4, 1), 2),
+            ((3, 5), 0),
+            ];
+
+        let (paths, cycles) = decompose(&flows, 0, 3);
+        assert_eq!(paths, vec![(vec![0, 1, 2, 3], 1)]);
+        assert_eq!(cycles.len(), 1);
+        let (ref cycle, amount) = cycles[0];
+        assert_eq!(amount, 2);
+        assert_eq!(cycle.len(), 3);
+        for &u in [1, 2, 4].iter() { assert!(cycle.contains(&u)); }
+    }
+
+    #[test]
+    fn through_source() {
+        // Net flow of 1 from 0 to 1, but 3 units go out along 0 -> 1 and 2 come back around.
+        let flows = vec![((0, 1), 3u32), ((1, 2), 2), ((2, 0), 2)];
+        let (paths, cycles) = decompose(&flows, 0, 1);
+        assert_eq!(paths, vec![(vec![0, 1], 1)]);
+        assert_eq!(cycles, vec![(vec![0, 1, 2], 2)]);
+    }
+}
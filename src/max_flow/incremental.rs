@@ -0,0 +1,198 @@
+//! Implements max flow that is kept up to date as the capacities of a network change, instead of
+//! being recomputed from scratch after every change.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use graph::Graph;
+use weight::Weight;
+use super::Residual;
+
+/// A max flow between a fixed source and sink of a network whose edges can be added or have
+/// their capacities raised or lowered afterwards. Each change starts from the previous max flow:
+/// a raised capacity only needs the new augmenting paths it opens up, and a lowered one first
+/// reroutes or cancels the flow that no longer fits, which touches only that much flow.
+///
+/// Augments with Dinic's algorithm, so each change runs in O(V^2 E) at worst, but usually much
+/// faster when the change is small compared to the flow.
+pub struct IncrementalMaxFlow<N, E> {
+    residual: Residual<N, E>,
+    edges: HashMap<(N, N), usize>,
+    source: usize,
+    sink: usize,
+}
+
+impl<N: Copy + Hash + Eq, E: Weight> IncrementalMaxFlow<N, E> {
+    /// Computes the max flow between S and T in a graph G with capacities on the edges.
+    pub fn new<G>(graph: &G, source: N, sink: N) -> Self
+        where G: Graph<NodeIndex = N, EdgeValue = E>
+    {
+        let mut residual = Residual::new(graph);
+        let (s, t) = (residual.node(source), residual.node(sink));
+        let edges = residual.edges.iter().enumerate().map(|(k, &(edge, _))| (edge, k)).collect();
+
+        let mut flow = IncrementalMaxFlow { residual, edges, source: s, sink: t };
+        flow.augment();
+        flow
+    }
+
+    /// Returns the value of the current max flow.
+    pub fn value(&self) -> E {
+        self.residual.value(self.source)
+    }
+
+    /// Returns the flow assigned to every edge, including the ones added since construction.
+    pub fn flows(&self) -> Vec<((N, N), E)> {
+        self.residual.flows()
+    }
+
+    /// Returns the capacity of the edge from `from` to `to`, or None if there is no such edge.
+    pub fn capacity(&self, from: N, to: N) -> Option<E> {
+        self.edges.get(&(from, to)).map(|&k| self.residual.edges[k].1)
+    }
+
+    /// Changes the capacity of the edge from `from` to `to`, adding the edge (and its nodes) if it
+    /// isn't in the network yet, and returns the value of the new max flow.
+    pub fn set_capacity(&mut self, from: N, to: N, capacity: E) -> E {
+        let k = match self.edges.get(&(from, to)) {
+            Some(&k) => k,
+            None => {
+                let k = self.residual.add_edge(from, to, E::zero());
+                self.edges.insert((from, to), k);
+                k
+            }
+        };
+
+        let flow = self.residual.capacities[2 * k + 1];
+        self.residual.edges[k].1 = capacity;
+        self.residual.rescale(capacity);
+        if !capacity.less_than(flow) {
+            self.residual.capacities[2 * k] = capacity - flow;
+            self.augment();
+            return self.value();
+        }
+
+        // Cut the flow on the edge down to its new capacity. That leaves `excess` too much flow
+        // going into u and too little leaving v, which is fixed by finding another way from u to
+        // v, and failing that by sending the flow back to where it came from.
+        let excess = flow - capacity;
+        self.residual.capacities[2 * k] = E::zero();
+        self.residual.capacities[2 * k + 1] = capacity;
+
+        let (s, t) = (self.source, self.sink);
+        let (u, v) = (self.residual.index[&from], self.residual.index[&to]);
+        if u != v {
+            let excess = excess - self.transfer(u, v, excess);
+            if u != s && u != t {
+                let rest = excess - self.transfer(u, s, excess);
+                self.transfer(u, t, rest);
+            }
+            if v != s && v != t {
+                let rest = excess - self.transfer(t, v, excess);
+                self.transfer(s, v, rest);
+            }
+        }
+
+        self.augment();
+        self.value()
+    }
+
+    // Pushes flow from source to sink until it is maximum again.
+    fn augment(&mut self) {
+        let (s, t) = (self.source, self.sink);
+        if s != t {
+            self.transfer(s, t, E::infinity());
+        }
+    }
+
+    // Moves up to `amount` flow from u to v through the residual network, leaving the balance
+    // of every other node as it was, and returns how much was moved.
+    fn transfer(&mut self, u: usize, v: usize, amount: E) -> E {
+        let mut moved = E::zero();
+        while moved.less_than(amount) {
+            let levels = self.residual.levels(u);
+            if levels[v].is_none() { break; }
+
+            let mut next = vec![0; self.residual.len()];
+            loop {
+                let pushed = self.residual.blocking_push(&levels, &mut next, u, v,
+                                                         amount.saturating_sub(moved));
+                if !pushed.is_above_zero(self.residual.scale) { break; }
+                moved = moved.saturating_add(pushed);
+            }
+        }
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use std::collections::HashMap;
+
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+    use max_flow::{Dinic, MaxFlow};
+
+    #[test]
+    fn simple() {
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 3u32);
+        graph.add_edge(v[0], v[2], 2);
+        graph.add_edge(v[1], v[2], 1);
+        graph.add_edge(v[1], v[3], 2);
+        graph.add_edge(v[2], v[3], 3);
+
+        let mut flow = IncrementalMaxFlow::new(&graph, v[0], v[3]);
+        assert_eq!(flow.value(), 5);
+
+        // Raising (0, 2) opens up a path; lowering (1, 3) forces flow onto (1, 2).
+        assert_eq!(flow.set_capacity(v[0], v[2], 4), 5);
+        assert_eq!(flow.set_capacity(v[1], v[3], 0), 3);
+        assert_eq!(flow.set_capacity(v[1], v[3], 5), 6);
+
+        // A brand new node with its own route to the sink.
+        assert_eq!(flow.set_capacity(v[0], 10, 4), 6);
+        assert_eq!(flow.set_capacity(10, v[3], 2), 8);
+        assert_eq!(flow.capacity(10, v[3]), Some(2));
+        assert_eq!(flow.capacity(v[3], 10), None);
+    }
+
+    #[test]
+    fn random_updates() {
+        for _ in 0..20 {
+            let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+            let v: Vec<_> = (0..8).map(|_| graph.add_node(())).collect();
+            let mut capacities: HashMap<(usize, usize), u32> = HashMap::new();
+            for _ in 0..20 {
+                let (from, to) = (rand::random::<usize>() % 8, rand::random::<usize>() % 8);
+                capacities.insert((v[from], v[to]), rand::random::<u32>() % 10);
+            }
+            for (&(from, to), &capacity) in capacities.iter() {
+                graph.add_edge(from, to, capacity);
+            }
+
+            let mut flow = IncrementalMaxFlow::new(&graph, v[0], v[7]);
+            for _ in 0..20 {
+                let (from, to) = (rand::random::<usize>() % 8, rand::random::<usize>() % 8);
+                let capacity = rand::random::<u32>() % 10;
+                capacities.insert((v[from], v[to]), capacity);
+                graph.add_edge(v[from], v[to], capacity);
+
+                let value = flow.set_capacity(v[from], v[to], capacity);
+                assert_eq!(value, Dinic.max_flow(&graph, v[0], v[7]).0);
+
+                let mut balance = [0i64; 8];
+                for ((from, to), f) in flow.flows().into_iter() {
+                    assert!(f <= capacities[&(from, to)]);
+                    balance[from] -= f as i64;
+                    balance[to] += f as i64;
+                }
+                assert!(balance[1..7].iter().all(|&b| b == 0));
+                assert_eq!(balance[7], value as i64);
+            }
+        }
+    }
+}
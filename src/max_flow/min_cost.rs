@@ -0,0 +1,274 @@
+//! Implements [minimum-cost flow](http://en.wikipedia.org/wiki/Minimum-cost_flow_problem) on
+//! graphs whose edges carry both a capacity and a per-unit cost.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::ops::{Mul, Neg};
+
+use graph::Graph;
+use weight::{Weight, Ordered};
+use super::{Flows, Residual};
+
+/// Weight and per-unit cost of an edge in a min-cost flow network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostEdge<E> {
+    pub capacity: E,
+    pub cost: E,
+}
+
+/// A cycle with negative total cost and room for more flow on every edge, given as the nodes
+/// along it in order. Pushing flow around it forever makes the cost unbounded.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NegativeCycle<N>(pub Vec<N>);
+
+/// Given a graph G whose edges have capacities and per-unit costs, source S, and sink T, returns
+/// the value of the max flow between S and T, the minimum total cost of any max flow, and the
+/// flow assigned to every edge in G.
+///
+/// Costs may be negative, so E has to be a signed type. If G contains a negative-cost cycle,
+/// returns that cycle instead.
+pub trait MinCostFlow<G, E> where G: Graph<EdgeValue = CostEdge<E>> {
+    #[allow(clippy::type_complexity)]
+    fn min_cost_flow(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+                     -> Result<(E, E, Flows<G::NodeIndex, E>),
+                               NegativeCycle<G::NodeIndex>>;
+}
+
+/// [Successive shortest paths](http://en.wikipedia.org/wiki/Minimum-cost_flow_problem) -- runs in
+/// O(F (E + V log V)) after an O(VE) Bellman-Ford pass, where F is the value of the max flow.
+/// Augments along cheapest paths found by Dijkstra's algorithm on costs reduced by Johnson
+/// potentials, which keeps every residual cost non-negative.
+pub struct SuccessiveShortestPaths;
+
+// Bellman-Ford from a virtual node with a zero-cost arc to every node. Returns distances that are
+// valid potentials for the residual network, or a negative cycle as a list of dense nodes.
+fn potentials<N, E>(residual: &Residual<N, E>, costs: &[E]) -> Result<Vec<E>, Vec<usize>>
+    where N: Copy + Hash + Eq, E: Weight
+{
+    let zero = E::zero();
+    let n = residual.len();
+    let mut distances = vec![zero; n];
+    let mut parent: Vec<Option<usize>> = vec![None; n];
+    let mut last = None;
+
+    for _ in 0..n {
+        last = None;
+        for u in 0..n {
+            for &arc in residual.adjacent[u].iter() {
+                let v = residual.heads[arc];
+                let distance = distances[u] + costs[arc];
+                if residual.has_room(arc) && distance.less_than(distances[v]) {
+                    distances[v] = distance;
+                    parent[v] = Some(arc);
+                    last = Some(v);
+                }
+            }
+        }
+        if last.is_none() { return Ok(distances); }
+    }
+
+    // Something still relaxed after n rounds, so following parents n times from it is
+    // guaranteed to land on the cycle.
+    let mut v = last.unwrap();
+    for _ in 0..n {
+        v = residual.heads[parent[v].unwrap() ^ 1];
+    }
+
+    let mut cycle = vec![v];
+    let mut u = residual.heads[parent[v].unwrap() ^ 1];
+    while u != v {
+        cycle.push(u);
+        u = residual.heads[parent[u].unwrap() ^ 1];
+    }
+    cycle.reverse();
+    Err(cycle)
+}
+
+// Dijkstra's algorithm from s using reduced costs, stopping as soon as t is settled. Returns the
+// parent arc of each node, or None if t is unreachable. Potentials are updated in place so that
+// reduced costs stay non-negative once flow is pushed along the path to t.
+fn cheapest_path<N, E>(residual: &Residual<N, E>, costs: &[E], potentials: &mut [E],
+                       s: usize, t: usize) -> Option<Vec<Option<usize>>>
+    where N: Copy + Hash + Eq, E: Weight
+{
+    let zero = E::zero();
+    let n = residual.len();
+    let mut distances: Vec<Option<E>> = vec![None; n];
+    let mut parent: Vec<Option<usize>> = vec![None; n];
+    let mut settled = vec![false; n];
+    let mut heap = BinaryHeap::new();
+
+    distances[s] = Some(zero);
+    heap.push(Reverse((Ordered(zero), s)));
+    while let Some(Reverse((Ordered(d), u))) = heap.pop() {
+        if settled[u] { continue; }
+        settled[u] = true;
+        if u == t { break; }
+
+        for &arc in residual.adjacent[u].iter() {
+            let v = residual.heads[arc];
+            if settled[v] || !residual.has_room(arc) { continue; }
+
+            let candidate = d + costs[arc] + potentials[u] - potentials[v];
+            if distances[v].is_none_or(|old| candidate.less_than(old)) {
+                distances[v] = Some(candidate);
+                parent[v] = Some(arc);
+                heap.push(Reverse((Ordered(candidate), v)));
+            }
+        }
+    }
+
+    if !settled[t] { return None; }
+
+    // Nodes that weren't settled are at least as far away as t.
+    let limit = distances[t].unwrap();
+    for u in 0..n {
+        let d = if settled[u] { distances[u].unwrap() } else { limit };
+        potentials[u] = potentials[u] + d;
+    }
+
+    Some(parent)
+}
+
+impl<G: Graph<EdgeValue = CostEdge<E>>, E> MinCostFlow<G, E> for SuccessiveShortestPaths
+    where E: Weight + Mul<Output = E> + Neg<Output = E>, G::NodeIndex: Hash + Eq
+{
+    fn min_cost_flow(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+                     -> Result<(E, E, Flows<G::NodeIndex, E>),
+                               NegativeCycle<G::NodeIndex>>
+    {
+        let edges = graph.edges();
+        let mut residual = Residual::from_edges(
+            graph.nodes(),
+            edges.iter().map(|&(from, to, edge)| ((from, to), edge.capacity)));
+        let costs: Vec<E> = edges.iter()
+            .flat_map(|&(_, _, edge)| vec![edge.cost, -edge.cost])
+            .collect();
+        let (s, t) = (residual.node(source), residual.node(sink));
+
+        let mut potentials = match potentials(&residual, &costs) {
+            Ok(potentials) => potentials,
+            Err(cycle) => {
+                return Err(NegativeCycle(cycle.into_iter().map(|u| residual.nodes[u]).collect()));
+            }
+        };
+
+        let mut cost = E::zero();
+        if s != t {
+            while let Some(parent) = cheapest_path(&residual, &costs, &mut potentials, s, t) {
+                let path = residual.trace(&parent, t);
+                let unit_cost = path.iter().fold(E::zero(), |acc, &arc| acc + costs[arc]);
+                let amount = residual.augment(&path);
+                cost = cost + amount * unit_cost;
+            }
+        }
+
+        Ok((residual.value(s), cost, residual.flows()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+
+    #[test]
+    fn simple() {
+        // One unit fits on the cheap top route and two more have to take the expensive bottom one.
+        let edges = [
+            (0, 1, 1, 1),
+            (1, 3, 1, 1),
+            (0, 2, 2, 5),
+            (2, 3, 2, 5),
+            (1, 2, 1, 1),
+        ];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..4 {
+            graph.add_node(());
+        }
+        for &(from, to, capacity, cost) in edges.iter() {
+            graph.add_edge(from, to, CostEdge { capacity, cost });
+        }
+
+        let (value, cost, flows) = SuccessiveShortestPaths.min_cost_flow(&graph, 0, 3).unwrap();
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        assert_eq!(value, 3);
+        assert_eq!(cost, 2 + 2 * 10);
+        assert_eq!(flows[&(0, 1)], 1);
+        assert_eq!(flows[&(1, 2)], 0);
+    }
+
+    #[test]
+    fn assignment() {
+        // Workers 1, 2, 3 to jobs 4, 5, 6 with source 0 and sink 7.
+        let costs = [[4, 1, 3], [2, 0, 5], [3, 2, 2]];
+        let mut edges = Vec::new();
+        for (i, row) in costs.iter().enumerate() {
+            edges.push((0, 1 + i, 1, 0));
+            edges.push((4 + i, 7, 1, 0));
+            for (j, &cost) in row.iter().enumerate() {
+                edges.push((1 + i, 4 + j, 1, cost));
+            }
+        }
+        let mut graph = AdjacencyList::new();
+        for _ in 0..8 {
+            graph.add_node(());
+        }
+        for &(from, to, capacity, cost) in edges.iter() {
+            graph.add_edge(from, to, CostEdge { capacity, cost });
+        }
+
+        let (value, cost, _) = SuccessiveShortestPaths.min_cost_flow(&graph, 0, 7).unwrap();
+        assert_eq!(value, 3);
+        assert_eq!(cost, 5);
+    }
+
+    #[test]
+    fn negative_costs() {
+        let edges = [(0, 1, 2, -3), (1, 2, 1, 1), (0, 2, 5, 0)];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..3 {
+            graph.add_node(());
+        }
+        for &(from, to, capacity, cost) in edges.iter() {
+            graph.add_edge(from, to, CostEdge { capacity, cost });
+        }
+        let (value, cost, _) = SuccessiveShortestPaths.min_cost_flow(&graph, 0, 2).unwrap();
+        assert_eq!(value, 6);
+        assert_eq!(cost, -2);
+    }
+
+    #[test]
+    fn fractional() {
+        let mut graph = AdjacencyList::new();
+        for _ in 0..3 {
+            graph.add_node(());
+        }
+        graph.add_edge(0, 1, CostEdge { capacity: 0.5, cost: 0.1 });
+        graph.add_edge(1, 2, CostEdge { capacity: 0.5, cost: 0.2 });
+        graph.add_edge(0, 2, CostEdge { capacity: 1.0, cost: 0.4 });
+
+        let (value, cost, _) = SuccessiveShortestPaths.min_cost_flow(&graph, 0, 2).unwrap();
+        assert!((value - 1.5f64).abs() < 1e-9);
+        assert!((cost - 0.55f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let edges = [(0, 1, 1, 1), (1, 2, 1, -2), (2, 1, 1, 1), (2, 3, 1, 1)];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..4 {
+            graph.add_node(());
+        }
+        for &(from, to, capacity, cost) in edges.iter() {
+            graph.add_edge(from, to, CostEdge { capacity, cost });
+        }
+        let NegativeCycle(mut cycle) = SuccessiveShortestPaths.min_cost_flow(&graph, 0, 3)
+            .unwrap_err();
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 2]);
+    }
+}
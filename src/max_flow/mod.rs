@@ -0,0 +1,1020 @@
+//! Implements [max flow algorithms](http://en.wikipedia.org/wiki/Maximum_flow_problem) on graphs.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::cmp::{min, max, Ordering};
+
+use graph::Graph;
+use weight::{self, Weight};
+
+pub mod min_cost;
+pub mod circulation;
+pub mod decomposition;
+pub mod multi_terminal;
+pub mod incremental;
+
+/// The flow on every edge, as ((from, to), flow) pairs.
+pub type Flows<N, E> = Vec<((N, N), E)>;
+
+/// Given a graph G with capacities on the edges, source S, and sink T, returns the value of the
+/// max flow between S and T along with the flow assigned to every edge in G.
+///
+/// If T is not reachable from S, the max flow is zero.
+pub trait MaxFlow<G: Graph> where G::EdgeValue: Weight {
+    fn max_flow(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+                -> (G::EdgeValue, Flows<G::NodeIndex, G::EdgeValue>);
+}
+
+/// Given a graph G with capacities on the edges, source S, and sink T, returns the nodes on the
+/// source side of a minimum S-T cut, the nodes on the sink side, and the saturated edges that
+/// cross from the source side to the sink side along with their capacities.
+///
+/// Any max flow algorithm gives a min cut: the source side is everything still reachable from S
+/// in the residual network of a max flow.
+pub trait MinCut<G: Graph> where G::EdgeValue: Weight {
+    #[allow(clippy::type_complexity)]
+    fn min_cut(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+               -> (Vec<G::NodeIndex>, Vec<G::NodeIndex>,
+                   Flows<G::NodeIndex, G::EdgeValue>);
+}
+
+impl<G: Graph, M: MaxFlow<G>> MinCut<G> for M
+    where G::EdgeValue: Weight, G::NodeIndex: Hash + Eq
+{
+    fn min_cut(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+               -> (Vec<G::NodeIndex>, Vec<G::NodeIndex>,
+                   Flows<G::NodeIndex, G::EdgeValue>)
+    {
+        let (_, flows) = self.max_flow(graph, source, sink);
+        let mut residual = Residual::with_flows(graph, &flows);
+        let s = residual.node(source);
+        let levels = residual.levels(s);
+
+        let (mut source_side, mut sink_side) = (Vec::new(), Vec::new());
+        for (u, &node) in residual.nodes.iter().enumerate() {
+            if levels[u].is_some() { source_side.push(node); } else { sink_side.push(node); }
+        }
+
+        let cut = residual.edges.iter()
+            .filter(|&&((from, to), _)| {
+                levels[residual.index[&from]].is_some() && levels[residual.index[&to]].is_none()
+            })
+            .cloned()
+            .collect();
+
+        (source_side, sink_side, cut)
+    }
+}
+
+// Residual network shared by the flow algorithms. Nodes are renumbered densely, and edge k of
+// the original graph becomes arc 2k (forward) and arc 2k + 1 (backward), so the reverse of
+// any arc is always arc ^ 1 and the flow on edge k is the residual capacity of arc 2k + 1.
+// Residual capacities within the tolerance of the largest finite capacity count as used up.
+struct Residual<N, E> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    adjacent: Vec<Vec<usize>>,
+    heads: Vec<usize>,
+    capacities: Vec<E>,
+    edges: Vec<((N, N), E)>,
+    scale: E,
+}
+
+impl<N: Copy + Hash + Eq, E: Weight> Residual<N, E> {
+    fn new<G>(graph: &G) -> Self where G: Graph<NodeIndex = N, EdgeValue = E> {
+        let edges = graph.edges().into_iter().map(|(from, to, &capacity)| ((from, to), capacity));
+        Residual::from_edges(graph.nodes(), edges)
+    }
+
+    fn from_edges<I>(nodes: Vec<N>, edges: I) -> Self where I: IntoIterator<Item = ((N, N), E)> {
+        let mut residual = Residual {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            adjacent: Vec::new(),
+            heads: Vec::new(),
+            capacities: Vec::new(),
+            edges: Vec::new(),
+            scale: E::zero(),
+        };
+
+        for node in nodes.into_iter() {
+            residual.node(node);
+        }
+
+        for ((from, to), capacity) in edges {
+            residual.add_edge(from, to, capacity);
+        }
+
+        residual
+    }
+
+    // Adds an edge with no flow on it, registering its nodes if needed, and returns its index.
+    fn add_edge(&mut self, from: N, to: N, capacity: E) -> usize {
+        let (u, v) = (self.node(from), self.node(to));
+        let arc = self.heads.len();
+        self.adjacent[u].push(arc);
+        self.adjacent[v].push(arc + 1);
+        self.heads.push(v);
+        self.heads.push(u);
+        self.capacities.push(capacity);
+        self.capacities.push(E::zero());
+        self.edges.push(((from, to), capacity));
+        self.rescale(capacity);
+        self.edges.len() - 1
+    }
+
+    // Takes a new capacity into account in the scale of the network.
+    fn rescale(&mut self, capacity: E) {
+        if capacity.compare(&E::infinity()) == Ordering::Less {
+            self.scale = weight::max(self.scale, capacity);
+        }
+    }
+
+    // Adds a node whose only arc carries up to the given capacity into u, and returns it. The arc
+    // isn't one of the network's edges, so it never shows up in the flows.
+    fn feed(&mut self, u: usize, capacity: E) -> usize {
+        let feeder = self.len();
+        let arc = self.heads.len();
+        self.nodes.push(self.nodes[u]);
+        self.adjacent.push(vec![arc]);
+        self.adjacent[u].push(arc + 1);
+        self.heads.push(u);
+        self.heads.push(feeder);
+        self.capacities.push(capacity);
+        self.capacities.push(E::zero());
+        feeder
+    }
+
+    // Total capacity leaving s, saturating at the largest value of the type.
+    fn supply(&self, s: usize) -> E {
+        self.adjacent[s].iter()
+            .filter(|&&arc| arc & 1 == 0)
+            .fold(E::zero(), |acc, &arc| acc.saturating_add(self.capacities[arc]))
+    }
+
+    // Builds the residual network left behind by pushing the given flow on each edge.
+    fn with_flows<G>(graph: &G, flows: &[((N, N), E)]) -> Self
+        where G: Graph<NodeIndex = N, EdgeValue = E>
+    {
+        let mut residual = Residual::new(graph);
+        let arcs: HashMap<(N, N), usize> = residual.edges.iter().enumerate()
+            .map(|(k, &(edge, _))| (edge, 2 * k))
+            .collect();
+
+        for &(edge, flow) in flows.iter() {
+            residual.push(arcs[&edge], flow);
+        }
+
+        residual
+    }
+
+    // Returns the dense index of a node, registering it if it hasn't been seen yet.
+    fn node(&mut self, node: N) -> usize {
+        if let Some(&u) = self.index.get(&node) {
+            return u;
+        }
+
+        let u = self.nodes.len();
+        self.nodes.push(node);
+        self.index.insert(node, u);
+        self.adjacent.push(Vec::new());
+        u
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    // Whether more flow fits through an arc.
+    fn has_room(&self, arc: usize) -> bool {
+        self.capacities[arc].is_above_zero(self.scale)
+    }
+
+    fn push(&mut self, arc: usize, amount: E) {
+        self.capacities[arc] = self.capacities[arc] - amount;
+        self.capacities[arc ^ 1] = self.capacities[arc ^ 1] + amount;
+    }
+
+    // Pushes the bottleneck capacity along a path of arcs and returns the amount pushed.
+    fn augment(&mut self, path: &[usize]) -> E {
+        let bottleneck = path.iter().map(|&arc| self.capacities[arc]).reduce(weight::min).unwrap();
+        for &arc in path.iter() {
+            self.push(arc, bottleneck);
+        }
+        bottleneck
+    }
+
+    // Depth-first search for any path of arcs with positive residual capacity from s to t.
+    fn find_path(&self, s: usize, t: usize) -> Option<Vec<usize>> {
+        let mut parent: Vec<Option<usize>> = vec![None; self.len()];
+        let mut visited = vec![false; self.len()];
+        let mut stack = vec![s];
+        visited[s] = true;
+
+        while let Some(u) = stack.pop() {
+            if u == t { break; }
+            for &arc in self.adjacent[u].iter() {
+                let v = self.heads[arc];
+                if !visited[v] && self.has_room(arc) {
+                    visited[v] = true;
+                    parent[v] = Some(arc);
+                    stack.push(v);
+                }
+            }
+        }
+
+        if !visited[t] || s == t { return None; }
+        Some(self.trace(&parent, t))
+    }
+
+    // Breadth-first search for a path with the fewest arcs from s to t, all with positive
+    // residual capacity of at least `delta`.
+    fn find_shortest_path(&self, s: usize, t: usize, delta: E) -> Option<Vec<usize>> {
+        let mut parent: Vec<Option<usize>> = vec![None; self.len()];
+        let mut visited = vec![false; self.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back(s);
+        visited[s] = true;
+
+        while let Some(u) = queue.pop_front() {
+            if u == t { break; }
+            for &arc in self.adjacent[u].iter() {
+                let v = self.heads[arc];
+                let capacity = self.capacities[arc];
+                if !visited[v] && capacity.is_above_zero(self.scale) && !capacity.less_than(delta) {
+                    visited[v] = true;
+                    parent[v] = Some(arc);
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        if !visited[t] || s == t { return None; }
+        Some(self.trace(&parent, t))
+    }
+
+    // Breadth-first distances from s using only arcs with positive residual capacity.
+    fn levels(&self, s: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        levels[s] = Some(0);
+        queue.push_back(s);
+
+        while let Some(u) = queue.pop_front() {
+            let next = levels[u].map(|l| l + 1);
+            for &arc in self.adjacent[u].iter() {
+                let v = self.heads[arc];
+                if levels[v].is_none() && self.has_room(arc) {
+                    levels[v] = next;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        levels
+    }
+
+    // Pushes up to `limit` flow from u to t along arcs that go up exactly one level. `next[u]`
+    // is the current arc of u: arcs before it are known to be saturated or lead to dead ends,
+    // so they are never looked at again during this phase. The search keeps its path in a stack
+    // of arcs rather than recursing, since the level graph can be as deep as the network.
+    fn blocking_push(&mut self, levels: &[Option<usize>], next: &mut [usize],
+                     u: usize, t: usize, limit: E) -> E {
+        let mut path: Vec<usize> = Vec::new();
+        let mut v = u;
+        loop {
+            if v == t {
+                let pushed = path.iter()
+                    .fold(limit, |amount, &arc| weight::min(amount, self.capacities[arc]));
+                if pushed.is_above_zero(self.scale) || path.is_empty() {
+                    for &arc in path.iter() {
+                        self.push(arc, pushed);
+                    }
+                    return pushed;
+                }
+            } else {
+                let mut advanced = false;
+                while next[v] < self.adjacent[v].len() {
+                    let arc = self.adjacent[v][next[v]];
+                    let w = self.heads[arc];
+                    let uphill = levels[w] == levels[v].map(|l| l + 1);
+                    if self.has_room(arc) && uphill {
+                        path.push(arc);
+                        v = w;
+                        advanced = true;
+                        break;
+                    }
+                    next[v] += 1;
+                }
+                if advanced { continue; }
+            }
+
+            // Dead end: back up one arc and skip it from now on.
+            match path.pop() {
+                Some(arc) => {
+                    v = self.heads[arc ^ 1];
+                    next[v] += 1;
+                }
+                None => return E::zero(),
+            }
+        }
+    }
+
+    // Walks parent arcs back from t to recover the path found by a search.
+    fn trace(&self, parent: &[Option<usize>], t: usize) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut v = t;
+        while let Some(arc) = parent[v] {
+            path.push(arc);
+            v = self.heads[arc ^ 1];
+        }
+        path.reverse();
+        path
+    }
+
+    fn flows(&self) -> Vec<((N, N), E)> {
+        self.edges.iter().enumerate()
+            .map(|(k, &(edge, _))| (edge, self.capacities[2 * k + 1]))
+            .collect()
+    }
+
+    // Net flow leaving s.
+    fn value(&self, s: usize) -> E {
+        let (mut out, mut into) = (E::zero(), E::zero());
+        for &arc in self.adjacent[s].iter() {
+            let flow = self.capacities[arc | 1];
+            if arc & 1 == 0 { out = out + flow; } else { into = into + flow; }
+        }
+        out - into
+    }
+}
+
+/// [Ford-Fulkerson](http://en.wikipedia.org/wiki/Ford%E2%80%93Fulkerson_algorithm) -- runs in
+/// O(EF) for integer capacities, where F is the value of the max flow.
+pub struct FordFulkerson;
+
+impl<G: Graph> MaxFlow<G> for FordFulkerson
+    where G::EdgeValue: Weight, G::NodeIndex: Hash + Eq
+{
+    fn max_flow(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+                -> (G::EdgeValue, Flows<G::NodeIndex, G::EdgeValue>)
+    {
+        let mut residual = Residual::new(graph);
+        let (s, t) = (residual.node(source), residual.node(sink));
+
+        while let Some(path) = residual.find_path(s, t) {
+            residual.augment(&path);
+        }
+
+        (residual.value(s), residual.flows())
+    }
+}
+
+/// [Edmonds-Karp](http://en.wikipedia.org/wiki/Edmonds%E2%80%93Karp_algorithm) -- runs in
+/// O(VE^2) by always augmenting along a shortest path, regardless of the capacities.
+pub struct EdmondsKarp;
+
+impl<G: Graph> MaxFlow<G> for EdmondsKarp
+    where G::EdgeValue: Weight, G::NodeIndex: Hash + Eq
+{
+    fn max_flow(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+                -> (G::EdgeValue, Flows<G::NodeIndex, G::EdgeValue>)
+    {
+        let mut residual = Residual::new(graph);
+        let (s, t) = (residual.node(source), residual.node(sink));
+
+        while let Some(path) = residual.find_shortest_path(s, t, G::EdgeValue::zero()) {
+            residual.augment(&path);
+        }
+
+        (residual.value(s), residual.flows())
+    }
+}
+
+/// [Capacity scaling](http://en.wikipedia.org/wiki/Ford%E2%80%93Fulkerson_algorithm) -- runs in
+/// O(E^2 log U), where U is the largest capacity divided by the smallest. Only augments along
+/// paths with residual capacity of at least some threshold, which starts out near U and is halved
+/// once no such path is left, so the running time doesn't depend on the value of the flow.
+pub struct CapacityScaling;
+
+impl<G: Graph> MaxFlow<G> for CapacityScaling
+    where G::EdgeValue: Weight, G::NodeIndex: Hash + Eq
+{
+    fn max_flow(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+                -> (G::EdgeValue, Flows<G::NodeIndex, G::EdgeValue>)
+    {
+        let mut residual = Residual::new(graph);
+        let (s, t) = (residual.node(source), residual.node(sink));
+
+        // Weights can't be divided, so the thresholds are built by doubling the smallest
+        // capacity and then used in reverse. The last phase takes any path at all, which
+        // mops up whatever is left below the smallest threshold.
+        let scale = residual.scale;
+        let positive = || residual.capacities.iter().cloned().filter(|c| c.is_above_zero(scale));
+        let (smallest, largest) = match (positive().reduce(weight::min),
+                                         positive().reduce(weight::max)) {
+            (Some(smallest), Some(largest)) => (smallest, largest),
+            _ => { return (residual.value(s), residual.flows()); }
+        };
+
+        let mut thresholds = vec![G::EdgeValue::zero()];
+        let mut delta = smallest;
+        while !largest.less_than(delta) {
+            thresholds.push(delta);
+            let next = delta.saturating_add(delta);
+            if !delta.less_than(next) { break; }
+            delta = next;
+        }
+
+        for &delta in thresholds.iter().rev() {
+            while let Some(path) = residual.find_shortest_path(s, t, delta) {
+                residual.augment(&path);
+            }
+        }
+
+        (residual.value(s), residual.flows())
+    }
+}
+
+/// [Dinic's algorithm](http://en.wikipedia.org/wiki/Dinic%27s_algorithm) -- runs in O(V^2 E) in
+/// general, and O(E sqrt(V)) on unit-capacity networks such as those built for bipartite matching.
+pub struct Dinic;
+
+impl<G: Graph> MaxFlow<G> for Dinic
+    where G::EdgeValue: Weight, G::NodeIndex: Hash + Eq
+{
+    fn max_flow(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+                -> (G::EdgeValue, Flows<G::NodeIndex, G::EdgeValue>)
+    {
+        let zero = G::EdgeValue::zero();
+        let mut residual = Residual::new(graph);
+        let (s, t) = (residual.node(source), residual.node(sink));
+        if s == t { return (zero, residual.flows()); }
+
+        // No single push can exceed the total capacity leaving the source.
+        let limit = residual.supply(s);
+
+        loop {
+            let levels = residual.levels(s);
+            if levels[t].is_none() { break; }
+
+            let mut next = vec![0; residual.len()];
+            while residual.blocking_push(&levels, &mut next, s, t, limit)
+                .is_above_zero(residual.scale) {}
+        }
+
+        (residual.value(s), residual.flows())
+    }
+}
+
+/// [Push-relabel](http://en.wikipedia.org/wiki/Push%E2%80%93relabel_maximum_flow_algorithm) with
+/// highest-label selection -- runs in O(V^2 sqrt(E)). Uses the gap heuristic and periodic global
+/// relabeling, which in practice make it the fastest choice here on large dense networks.
+pub struct PushRelabel;
+
+// Bookkeeping for push-relabel on top of a residual network.
+struct Preflow<E> {
+    heights: Vec<usize>,
+    excess: Vec<E>,
+    current: Vec<usize>,
+    // number of nodes at each height below n, for the gap heuristic
+    counts: Vec<usize>,
+    // active nodes by height; entries may be stale and are checked when popped
+    buckets: Vec<Vec<usize>>,
+    highest: usize,
+    relabels: usize,
+}
+
+impl<E: Weight> Preflow<E> {
+    // Saturates every arc out of s and computes the initial labels.
+    fn new<N: Copy + Hash + Eq>(residual: &mut Residual<N, E>, s: usize, t: usize) -> Self {
+        let n = residual.len();
+        let mut preflow = Preflow {
+            heights: vec![0; n],
+            excess: vec![E::zero(); n],
+            current: vec![0; n],
+            counts: vec![0; n + 1],
+            buckets: vec![Vec::new(); n + 1],
+            highest: 0,
+            relabels: 0,
+        };
+
+        for i in 0..residual.adjacent[s].len() {
+            let arc = residual.adjacent[s][i];
+            let capacity = residual.capacities[arc];
+            let v = residual.heads[arc];
+            if capacity.is_above_zero(residual.scale) && v != s {
+                residual.push(arc, capacity);
+                preflow.excess[v] = preflow.excess[v] + capacity;
+            }
+        }
+
+        preflow.global_relabel(residual, s, t);
+        preflow
+    }
+
+    // Sets every label to the exact residual distance to t (or n if t is unreachable), then
+    // rebuilds the active buckets from scratch.
+    fn global_relabel<N: Copy + Hash + Eq>(&mut self, residual: &Residual<N, E>, s: usize, t: usize) {
+        let n = residual.len();
+        for h in self.heights.iter_mut() { *h = n; }
+        for c in self.counts.iter_mut() { *c = 0; }
+        for b in self.buckets.iter_mut() { b.clear(); }
+        for c in self.current.iter_mut() { *c = 0; }
+
+        let mut queue = VecDeque::new();
+        self.heights[t] = 0;
+        queue.push_back(t);
+        while let Some(v) = queue.pop_front() {
+            self.counts[self.heights[v]] += 1;
+            for &arc in residual.adjacent[v].iter() {
+                let u = residual.heads[arc];
+                if u != s && self.heights[u] == n && residual.has_room(arc ^ 1) {
+                    self.heights[u] = self.heights[v] + 1;
+                    queue.push_back(u);
+                }
+            }
+        }
+        self.heights[t] = 0;
+
+        self.highest = 0;
+        for u in 0..n {
+            let active = self.excess[u].is_above_zero(residual.scale) && self.heights[u] < n;
+            if u != s && u != t && active {
+                self.buckets[self.heights[u]].push(u);
+                self.highest = max(self.highest, self.heights[u]);
+            }
+        }
+        self.relabels = 0;
+    }
+
+    fn relabel<N: Copy + Hash + Eq>(&mut self, residual: &Residual<N, E>, u: usize, s: usize) {
+        let n = residual.len();
+        let old = self.heights[u];
+        let new = residual.adjacent[u].iter()
+            .filter(|&&arc| residual.has_room(arc))
+            .map(|&arc| self.heights[residual.heads[arc]] + 1)
+            .min()
+            .map_or(n, |h| min(h, n));
+
+        self.counts[old] -= 1;
+        self.heights[u] = new;
+        if new < n { self.counts[new] += 1; }
+        self.current[u] = 0;
+        self.relabels += 1;
+
+        // Gap heuristic: if no node is left at the old height, nothing above it can reach t.
+        if self.counts[old] == 0 {
+            for v in 0..n {
+                let h = self.heights[v];
+                if v != s && h > old && h < n {
+                    self.counts[h] -= 1;
+                    self.heights[v] = n;
+                }
+            }
+        }
+    }
+
+    // Pushes excess out of u until it is gone or u can no longer reach t.
+    fn discharge<N: Copy + Hash + Eq>(&mut self, residual: &mut Residual<N, E>,
+                                      u: usize, s: usize, t: usize) {
+        let n = residual.len();
+
+        while self.excess[u].is_above_zero(residual.scale) && self.heights[u] < n {
+            if self.current[u] == residual.adjacent[u].len() {
+                self.relabel(residual, u, s);
+                continue;
+            }
+
+            let arc = residual.adjacent[u][self.current[u]];
+            let v = residual.heads[arc];
+            if residual.has_room(arc) && self.heights[u] == self.heights[v] + 1 {
+                let amount = weight::min(self.excess[u], residual.capacities[arc]);
+                residual.push(arc, amount);
+                if self.excess[v].is_zero(residual.scale) && v != s && v != t {
+                    self.buckets[self.heights[v]].push(v);
+                    self.highest = max(self.highest, self.heights[v]);
+                }
+                self.excess[u] = self.excess[u] - amount;
+                self.excess[v] = self.excess[v] + amount;
+            } else {
+                self.current[u] += 1;
+            }
+        }
+    }
+
+    // Phase one: computes a maximum preflow, leaving stranded excess on nodes cut off from t.
+    fn maximize<N: Copy + Hash + Eq>(&mut self, residual: &mut Residual<N, E>, s: usize, t: usize) {
+        let n = residual.len();
+        loop {
+            let u = match self.buckets[self.highest].pop() {
+                Some(u) => u,
+                None if self.highest == 0 => break,
+                None => { self.highest -= 1; continue; }
+            };
+            if self.heights[u] != self.highest { continue; }
+            if self.excess[u].is_zero(residual.scale) { continue; }
+
+            self.discharge(residual, u, s, t);
+            if self.relabels >= n {
+                self.global_relabel(residual, s, t);
+            }
+        }
+    }
+
+    // Phase two: turns the preflow into a flow by pushing stranded excess back to s. Labels
+    // start at n plus the residual distance to s, so every push moves excess closer to s.
+    fn return_excess<N: Copy + Hash + Eq>(&mut self, residual: &mut Residual<N, E>,
+                                          s: usize, t: usize) {
+        let n = residual.len();
+        for h in self.heights.iter_mut() { *h = 2 * n; }
+        for c in self.current.iter_mut() { *c = 0; }
+
+        let mut queue = VecDeque::new();
+        self.heights[s] = n;
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            for &arc in residual.adjacent[v].iter() {
+                let u = residual.heads[arc];
+                if self.heights[u] == 2 * n && residual.has_room(arc ^ 1) {
+                    self.heights[u] = self.heights[v] + 1;
+                    queue.push_back(u);
+                }
+            }
+        }
+
+        let mut active: VecDeque<usize> = (0..n)
+            .filter(|&u| u != s && u != t && self.excess[u].is_above_zero(residual.scale))
+            .collect();
+        while let Some(u) = active.pop_front() {
+            while self.excess[u].is_above_zero(residual.scale) {
+                if self.current[u] == residual.adjacent[u].len() {
+                    self.heights[u] = residual.adjacent[u].iter()
+                        .filter(|&&arc| residual.has_room(arc))
+                        .map(|&arc| self.heights[residual.heads[arc]] + 1)
+                        .min()
+                        .unwrap();
+                    self.current[u] = 0;
+                    continue;
+                }
+
+                let arc = residual.adjacent[u][self.current[u]];
+                let v = residual.heads[arc];
+                if residual.has_room(arc) && self.heights[u] == self.heights[v] + 1 {
+                    let amount = weight::min(self.excess[u], residual.capacities[arc]);
+                    residual.push(arc, amount);
+                    if self.excess[v].is_zero(residual.scale) && v != s && v != t {
+                        active.push_back(v);
+                    }
+                    self.excess[u] = self.excess[u] - amount;
+                    self.excess[v] = self.excess[v] + amount;
+                } else {
+                    self.current[u] += 1;
+                }
+            }
+        }
+    }
+}
+
+impl<G: Graph> MaxFlow<G> for PushRelabel
+    where G::EdgeValue: Weight, G::NodeIndex: Hash + Eq
+{
+    fn max_flow(&self, graph: &G, source: G::NodeIndex, sink: G::NodeIndex)
+                -> (G::EdgeValue, Flows<G::NodeIndex, G::EdgeValue>)
+    {
+        let mut residual = Residual::new(graph);
+        let (s, t) = (residual.node(source), residual.node(sink));
+
+        if s == t { return (G::EdgeValue::zero(), residual.flows()); }
+
+        // Excess only ever comes from the source, so feeding it through a single arc holding the
+        // total capacity out of s keeps every node's excess within that total.
+        let supply = residual.supply(s);
+        let feeder = residual.feed(s, supply);
+        let mut preflow = Preflow::new(&mut residual, feeder, t);
+        preflow.maximize(&mut residual, feeder, t);
+        preflow.return_excess(&mut residual, feeder, t);
+
+        (residual.value(feeder), residual.flows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_imports)]
+    extern crate rand;
+
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+
+    // Checks capacity and conservation constraints, and that the flow leaving s is `value`.
+    fn check_flow(graph: &AdjacencyList<(), u32>, s: usize, t: usize,
+                  value: u32, flows: &[((usize, usize), u32)]) {
+        let mut net: HashMap<usize, i64> = HashMap::new();
+        for &((from, to), flow) in flows.iter() {
+            let (_, _, &capacity) = graph.edges().into_iter()
+                .find(|&(u, v, _)| (u, v) == (from, to))
+                .unwrap();
+            assert!(flow <= capacity);
+            *net.entry(from).or_insert(0) -= flow as i64;
+            *net.entry(to).or_insert(0) += flow as i64;
+        }
+
+        for (&node, &balance) in net.iter() {
+            if node == s {
+                assert_eq!(balance, -(value as i64));
+            } else if node == t {
+                assert_eq!(balance, value as i64);
+            } else {
+                assert_eq!(balance, 0);
+            }
+        }
+    }
+
+    fn random_graph(nodes: usize, edges: usize, max_capacity: u32) -> AdjacencyList<(), u32> {
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..nodes).map(|_| graph.add_node(())).collect();
+        for _ in 0..edges {
+            let (from, to) = (rand::random::<usize>() % nodes, rand::random::<usize>() % nodes);
+            graph.add_edge(v[from], v[to], rand::random::<u32>() % max_capacity);
+        }
+        graph
+    }
+
+    #[test]
+    fn simple() {
+        let mut graph = AdjacencyList::new();
+        let mut vertices = Vec::new();
+
+        for _ in 0..4 {
+            vertices.push(graph.add_node(()));
+        }
+
+        let edges = vec![
+            ((vertices[0], vertices[1]), 10usize),
+            ((vertices[0], vertices[2]), 10),
+            ((vertices[1], vertices[2]), 1),
+            ((vertices[1], vertices[3]), 10),
+            ((vertices[2], vertices[3]), 10),
+            ];
+
+        for ((from, to), weight) in edges.into_iter() {
+            graph.add_edge(from, to, weight);
+        }
+
+        let (value, flows) = FordFulkerson.max_flow(&graph, vertices[0], vertices[3]);
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        assert_eq!(value, 20);
+        assert_eq!(flows[&(vertices[0], vertices[1])], 10);
+        assert_eq!(flows[&(vertices[0], vertices[2])], 10);
+        assert_eq!(flows[&(vertices[1], vertices[2])], 0);
+        assert_eq!(flows[&(vertices[1], vertices[3])], 10);
+        assert_eq!(flows[&(vertices[2], vertices[3])], 10);
+    }
+
+    #[test]
+    fn reverse_edges() {
+        // The only way to reach the max flow of 2 is to cancel flow on (1, 2) if the first
+        // augmenting path found is 0 -> 1 -> 2 -> 3.
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 1usize);
+        graph.add_edge(v[0], v[2], 1);
+        graph.add_edge(v[1], v[2], 1);
+        graph.add_edge(v[1], v[3], 1);
+        graph.add_edge(v[2], v[3], 1);
+
+        let (value, flows) = FordFulkerson.max_flow(&graph, v[0], v[3]);
+        assert_eq!(value, 2);
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        assert_eq!(flows[&(v[1], v[2])], 0);
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 5usize);
+
+        let (value, flows) = FordFulkerson.max_flow(&graph, v[0], v[2]);
+        assert_eq!(value, 0);
+        assert_eq!(flows, vec![((v[0], v[1]), 0)]);
+    }
+
+    #[test]
+    fn edmonds_karp() {
+        // Large capacities on a graph where a bad choice of paths would augment one unit at a
+        // time through the middle edge.
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 1_000_000_000u64);
+        graph.add_edge(v[0], v[2], 1_000_000_000);
+        graph.add_edge(v[1], v[2], 1);
+        graph.add_edge(v[1], v[3], 1_000_000_000);
+        graph.add_edge(v[2], v[3], 1_000_000_000);
+
+        let (value, flows) = EdmondsKarp.max_flow(&graph, v[0], v[3]);
+        assert_eq!(value, 2_000_000_000);
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        assert_eq!(flows[&(v[1], v[2])], 0);
+    }
+
+    #[test]
+    fn capacity_scaling() {
+        // Capacities spanning many orders of magnitude, with a few tiny edges mixed in.
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..5).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 1_000_000_000_000u64);
+        graph.add_edge(v[0], v[2], 3);
+        graph.add_edge(v[1], v[2], 7_000_000);
+        graph.add_edge(v[1], v[3], 1);
+        graph.add_edge(v[2], v[4], 5_000_000);
+        graph.add_edge(v[3], v[4], 1_000_000_000_000);
+
+        let (value, flows) = CapacityScaling.max_flow(&graph, v[0], v[4]);
+        assert_eq!(value, 5_000_001);
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        assert_eq!(flows[&(v[2], v[4])], 5_000_000);
+        assert_eq!(flows[&(v[1], v[3])], 1);
+        assert_eq!(CapacityScaling.max_flow(&graph, v[4], v[0]).0, 0);
+    }
+
+    #[test]
+    fn dinic() {
+        // Bipartite matching between {1, 2, 3} and {4, 5, 6} with source 0 and sink 7, where
+        // 1 and 2 both prefer 4 and only a perfect matching saturates every edge to the sink.
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..8).map(|_| graph.add_node(())).collect();
+        for &left in [1, 2, 3].iter() { graph.add_edge(v[0], v[left], 1u32); }
+        for &right in [4, 5, 6].iter() { graph.add_edge(v[right], v[7], 1); }
+        graph.add_edge(v[1], v[4], 1);
+        graph.add_edge(v[2], v[4], 1);
+        graph.add_edge(v[2], v[5], 1);
+        graph.add_edge(v[3], v[5], 1);
+        graph.add_edge(v[3], v[6], 1);
+
+        let (value, flows) = Dinic.max_flow(&graph, v[0], v[7]);
+        assert_eq!(value, 3);
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        assert_eq!(flows[&(v[1], v[4])], 1);
+        assert_eq!(flows[&(v[2], v[5])], 1);
+        assert_eq!(flows[&(v[3], v[6])], 1);
+
+        let (value, _) = Dinic.max_flow(&graph, v[0], v[0]);
+        assert_eq!(value, 0);
+    }
+
+    #[test]
+    fn long_path() {
+        // The level graph is as deep as the path, which mustn't cost a stack frame per level.
+        let n = 100_000;
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+        for i in 1..n {
+            graph.add_edge(v[i - 1], v[i], 1 + (i % 7) as u32);
+        }
+
+        assert_eq!(Dinic.max_flow(&graph, v[0], v[n - 1]).0, 1);
+        let mut flow = incremental::IncrementalMaxFlow::new(&graph, v[0], v[n - 1]);
+        assert_eq!(flow.set_capacity(v[0], v[1], 5), 1);
+    }
+
+    #[test]
+    fn push_relabel() {
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        // 3 is a dead end that receives excess and has to return it to the source.
+        graph.add_edge(v[0], v[1], 16u32);
+        graph.add_edge(v[0], v[2], 13);
+        graph.add_edge(v[1], v[3], 12);
+        graph.add_edge(v[2], v[1], 4);
+        graph.add_edge(v[2], v[4], 14);
+        graph.add_edge(v[3], v[2], 9);
+        graph.add_edge(v[4], v[5], 4);
+        graph.add_edge(v[1], v[5], 7);
+
+        let (value, flows) = PushRelabel.max_flow(&graph, v[0], v[5]);
+        assert_eq!(value, 11);
+        check_flow(&graph, v[0], v[5], value, &flows);
+    }
+
+    #[test]
+    fn huge_capacities() {
+        // Saturating both paths out of 0 leaves node 3 with twice the excess a u32 can hold.
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..5).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], u32::MAX);
+        graph.add_edge(v[0], v[2], u32::MAX);
+        graph.add_edge(v[1], v[3], u32::MAX);
+        graph.add_edge(v[2], v[3], u32::MAX);
+        graph.add_edge(v[3], v[4], 1);
+        let t = v[4];
+
+        assert_eq!(FordFulkerson.max_flow(&graph, v[0], t).0, 1);
+        assert_eq!(EdmondsKarp.max_flow(&graph, v[0], t).0, 1);
+        assert_eq!(CapacityScaling.max_flow(&graph, v[0], t).0, 1);
+        assert_eq!(Dinic.max_flow(&graph, v[0], t).0, 1);
+        let (value, flows) = PushRelabel.max_flow(&graph, v[0], t);
+        assert_eq!(value, 1);
+        check_flow(&graph, v[0], t, value, &flows);
+    }
+
+    #[test]
+    fn random_agreement() {
+        for _ in 0..50 {
+            let graph = random_graph(12, 40, 20);
+            let (s, t) = (0, 11);
+            let (expected, flows) = EdmondsKarp.max_flow(&graph, s, t);
+            check_flow(&graph, s, t, expected, &flows);
+
+            let results = vec![
+                FordFulkerson.max_flow(&graph, s, t),
+                CapacityScaling.max_flow(&graph, s, t),
+                Dinic.max_flow(&graph, s, t),
+                PushRelabel.max_flow(&graph, s, t),
+                ];
+            for (value, flows) in results.into_iter() {
+                assert_eq!(value, expected);
+                check_flow(&graph, s, t, value, &flows);
+            }
+        }
+    }
+
+    #[test]
+    fn floats() {
+        // Tenths don't add up exactly in binary, so this relies on the epsilon in `Weight`.
+        for _ in 0..20 {
+            let graph = random_graph(10, 30, 20);
+            let (expected, _) = Dinic.max_flow(&graph, 0, 9);
+
+            let mut scaled: AdjacencyList<(), f64> = AdjacencyList::new();
+            for _ in 0..10 { scaled.add_node(()); }
+            for (from, to, &capacity) in graph.edges().into_iter() {
+                scaled.add_edge(from, to, capacity as f64 / 10.0);
+            }
+
+            let results = vec![
+                FordFulkerson.max_flow(&scaled, 0, 9),
+                EdmondsKarp.max_flow(&scaled, 0, 9),
+                CapacityScaling.max_flow(&scaled, 0, 9),
+                Dinic.max_flow(&scaled, 0, 9),
+                PushRelabel.max_flow(&scaled, 0, 9),
+                ];
+            for (value, _) in results.into_iter() {
+                assert!((value - expected as f64 / 10.0).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn tiny_floats() {
+        // Capacities far below any fixed epsilon still count, since the tolerance is relative.
+        for _ in 0..20 {
+            let graph = random_graph(10, 30, 20);
+            let (expected, _) = Dinic.max_flow(&graph, 0, 9);
+
+            let mut scaled: AdjacencyList<(), f64> = AdjacencyList::new();
+            for _ in 0..10 { scaled.add_node(()); }
+            for (from, to, &capacity) in graph.edges().into_iter() {
+                scaled.add_edge(from, to, capacity as f64 * 1e-11);
+            }
+
+            let results = vec![
+                FordFulkerson.max_flow(&scaled, 0, 9),
+                EdmondsKarp.max_flow(&scaled, 0, 9),
+                CapacityScaling.max_flow(&scaled, 0, 9),
+                Dinic.max_flow(&scaled, 0, 9),
+                PushRelabel.max_flow(&scaled, 0, 9),
+                ];
+            for (value, _) in results.into_iter() {
+                assert!((value - expected as f64 * 1e-11).abs() < 1e-18);
+            }
+        }
+    }
+
+    #[test]
+    fn min_cut() {
+        let mut graph = AdjacencyList::new();
+        let v: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 16u32);
+        graph.add_edge(v[0], v[2], 13);
+        graph.add_edge(v[1], v[3], 12);
+        graph.add_edge(v[2], v[1], 4);
+        graph.add_edge(v[2], v[4], 14);
+        graph.add_edge(v[3], v[2], 9);
+        graph.add_edge(v[3], v[5], 20);
+        graph.add_edge(v[4], v[3], 7);
+        graph.add_edge(v[4], v[5], 4);
+
+        let (mut source_side, mut sink_side, mut cut) = Dinic.min_cut(&graph, v[0], v[5]);
+        source_side.sort();
+        sink_side.sort();
+        cut.sort();
+        assert_eq!(source_side, vec![v[0], v[1], v[2], v[4]]);
+        assert_eq!(sink_side, vec![v[3], v[5]]);
+        assert_eq!(cut, vec![((v[1], v[3]), 12), ((v[4], v[3]), 7), ((v[4], v[5]), 4)]);
+
+        let (value, _) = Dinic.max_flow(&graph, v[0], v[5]);
+        assert_eq!(cut.iter().fold(0, |acc, &(_, c)| acc + c), value);
+    }
+}
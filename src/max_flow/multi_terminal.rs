@@ -0,0 +1,147 @@
+//! Implements max flow with several sources and sinks, and with capacities on nodes as well as
+//! edges, by reduction to an ordinary single source, single sink max flow.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use graph::{AdjacencyList, Graph};
+use weight::Weight;
+use super::{Flows, MaxFlow};
+
+/// Given a graph G with capacities on the edges, a set of sources, a set of sinks, and a
+/// capacity for some of the nodes, returns the value of the max flow from the sources to the
+/// sinks along with the flow assigned to every edge in G. Nodes missing from `capacities` can
+/// carry any amount of flow, and a node's capacity limits the flow through it even if it is a
+/// source or a sink.
+///
+/// Self-loops never carry flow. Panics if a node is both a source and a sink.
+pub trait MultiTerminalMaxFlow<G: Graph> where G::EdgeValue: Weight {
+    fn multi_terminal_max_flow(&self, graph: &G, sources: &[G::NodeIndex], sinks: &[G::NodeIndex],
+                               capacities: &HashMap<G::NodeIndex, G::EdgeValue>)
+                               -> (G::EdgeValue, Flows<G::NodeIndex, G::EdgeValue>);
+}
+
+/// Any max flow algorithm handles this: a super source feeds every source and every sink drains
+/// into a super sink, and each node with a capacity is split into an entry half and an exit half
+/// joined by a single edge with that capacity.
+impl<G: Graph, M> MultiTerminalMaxFlow<G> for M
+    where G::EdgeValue: Weight, G::NodeIndex: Hash + Eq,
+          M: MaxFlow<AdjacencyList<(), G::EdgeValue>>
+{
+    fn multi_terminal_max_flow(&self, graph: &G, sources: &[G::NodeIndex], sinks: &[G::NodeIndex],
+                               capacities: &HashMap<G::NodeIndex, G::EdgeValue>)
+                               -> (G::EdgeValue, Flows<G::NodeIndex, G::EdgeValue>)
+    {
+        let zero = G::EdgeValue::zero();
+        let nodes = graph.nodes();
+        let edges = graph.edges();
+        let n = nodes.len();
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+
+        // Node u is entered at u and left from exit[u], which is only different if u is split.
+        let mut network = AdjacencyList::new();
+        for _ in 0..n {
+            network.add_node(());
+        }
+        let mut exit: Vec<usize> = (0..n).collect();
+        for (u, node) in nodes.iter().enumerate() {
+            if let Some(&capacity) = capacities.get(node) {
+                exit[u] = network.add_node(());
+                network.add_edge(u, exit[u], capacity);
+            }
+        }
+
+        // No source can send more than its edges carry out, nor any sink take more than comes in.
+        let (mut outgoing, mut incoming) = (vec![zero; n], vec![zero; n]);
+        for &(from, to, &capacity) in edges.iter() {
+            let (u, v) = (index[&from], index[&to]);
+            if u == v { continue; }
+            network.add_edge(exit[u], v, capacity);
+            outgoing[u] = outgoing[u].saturating_add(capacity);
+            incoming[v] = incoming[v].saturating_add(capacity);
+        }
+
+        let (source, sink) = (network.add_node(()), network.add_node(()));
+        let mut is_source = vec![false; n];
+        for node in sources.iter() {
+            let u = index[node];
+            is_source[u] = true;
+            network.add_edge(source, u, outgoing[u]);
+        }
+        for node in sinks.iter() {
+            let v = index[node];
+            if is_source[v] {
+                panic!("a node can't be both a source and a sink");
+            }
+            network.add_edge(exit[v], sink, incoming[v]);
+        }
+
+        let (value, flows) = self.max_flow(&network, source, sink);
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        let flows = edges.iter()
+            .map(|&(from, to, _)| {
+                let (u, v) = (index[&from], index[&to]);
+                ((from, to), if u == v { zero } else { flows[&(exit[u], v)] })
+            })
+            .collect();
+
+        (value, flows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+    use max_flow::{Dinic, PushRelabel};
+
+    #[test]
+    fn multiple_terminals() {
+        // Sources 0 and 1 share the bottleneck (2, 3), while 1 also reaches sink 5 directly.
+        let edges = [(0, 2, 4), (1, 2, 4), (2, 3, 5), (3, 4, 3), (3, 5, 3), (1, 5, 2)];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..6 {
+            graph.add_node(());
+        }
+        for &(from, to, capacity) in edges.iter() {
+            graph.add_edge(from, to, capacity);
+        }
+        let (value, flows) = Dinic.multi_terminal_max_flow(&graph, &[0, 1], &[4, 5], &HashMap::new());
+        assert_eq!(value, 7);
+
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        assert_eq!(flows[&(2, 3)], 5);
+        assert_eq!(flows[&(1, 5)], 2);
+        assert_eq!(flows[&(0, 2)] + flows[&(1, 2)], 5);
+    }
+
+    #[test]
+    fn node_capacities() {
+        // Two disjoint routes from 0 to 3 through nodes 1 and 2.
+        let edges = [(0, 1, 5), (0, 2, 5), (1, 3, 5), (2, 3, 5)];
+        let mut graph = AdjacencyList::new();
+        for _ in 0..4 {
+            graph.add_node(());
+        }
+        for &(from, to, capacity) in edges.iter() {
+            graph.add_edge(from, to, capacity);
+        }
+        let capacities: HashMap<_, _> = vec![(1, 2), (2, 3)].into_iter().collect();
+        let (value, flows) = PushRelabel.multi_terminal_max_flow(&graph, &[0], &[3], &capacities);
+        assert_eq!(value, 5);
+
+        let flows: HashMap<_, _> = flows.into_iter().collect();
+        assert_eq!(flows[&(0, 1)], 2);
+        assert_eq!(flows[&(2, 3)], 3);
+
+        // Capacities on the terminals themselves count too.
+        let capacities: HashMap<_, _> = vec![(0, 4)].into_iter().collect();
+        let (value, _) = Dinic.multi_terminal_max_flow(&graph, &[0], &[3], &capacities);
+        assert_eq!(value, 4);
+
+        let (value, _) = Dinic.multi_terminal_max_flow(&graph, &[0], &[3], &HashMap::new());
+        assert_eq!(value, Dinic.max_flow(&graph, 0, 3).0);
+    }
+}
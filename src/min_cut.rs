@@ -0,0 +1,304 @@
+//! Implements [global minimum cut algorithms](http://en.wikipedia.org/wiki/Minimum_cut) on
+//! undirected graphs.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use graph::{AdjacencyList, Graph};
+use max_flow::{MaxFlow, MinCut};
+use weight::{self, Ordered, Weight};
+
+/// Given a graph G with weights on the edges, returns the weight of a minimum cut of G along with
+/// the nodes on either side of it. Unlike a min S-T cut, no source or sink has to be chosen.
+///
+/// Every edge is treated as undirected, so an edge stored in both directions counts twice.
+/// Assumes G has at least 2 nodes.
+pub trait GlobalMinCut<G: Graph> where G::EdgeValue: Weight {
+    fn global_min_cut(&self, graph: &G)
+                      -> (G::EdgeValue, Vec<G::NodeIndex>, Vec<G::NodeIndex>);
+}
+
+/// [Stoer-Wagner](http://en.wikipedia.org/wiki/Stoer%E2%80%93Wagner_algorithm) -- runs in O(V^3)
+/// by repeatedly finding a minimum cut between the last two nodes of a maximum adjacency ordering
+/// and then merging them.
+pub struct StoerWagner;
+
+impl<G: Graph> GlobalMinCut<G> for StoerWagner
+    where G::EdgeValue: Weight, G::NodeIndex: Hash + Eq
+{
+    fn global_min_cut(&self, graph: &G)
+                      -> (G::EdgeValue, Vec<G::NodeIndex>, Vec<G::NodeIndex>)
+    {
+        let zero = G::EdgeValue::zero();
+        let nodes = graph.nodes();
+        let n = nodes.len();
+        if n < 2 {
+            panic!("need at least 2 nodes to find a global min cut, have {}", n);
+        }
+
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let mut weights = vec![vec![zero; n]; n];
+        for (from, to, &weight) in graph.edges().into_iter() {
+            let (u, v) = (index[&from], index[&to]);
+            if u == v { continue; }
+            weights[u][v] = weights[u][v] + weight;
+            weights[v][u] = weights[v][u] + weight;
+        }
+
+        // Each remaining node stands for the group of original nodes merged into it.
+        let mut groups: Vec<Vec<usize>> = (0..n).map(|u| vec![u]).collect();
+        let mut active: Vec<usize> = (0..n).collect();
+        let mut best: Option<(G::EdgeValue, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            let mut added = vec![false; n];
+            let mut connectivity = vec![zero; n];
+            let mut previous = active[0];
+
+            for k in 0..active.len() {
+                let v = *active.iter()
+                    .filter(|&&v| !added[v])
+                    .max_by_key(|&&v| Ordered(connectivity[v]))
+                    .unwrap();
+                added[v] = true;
+
+                if k + 1 < active.len() {
+                    for &u in active.iter() {
+                        connectivity[u] = connectivity[u] + weights[v][u];
+                    }
+                    previous = v;
+                    continue;
+                }
+
+                // The last node added is separated from everything else by a minimum cut
+                // between it and the node added before it.
+                if best.as_ref().is_none_or(|&(weight, _)| connectivity[v].less_than(weight)) {
+                    best = Some((connectivity[v], groups[v].clone()));
+                }
+
+                for &u in active.iter() {
+                    weights[previous][u] = weights[previous][u] + weights[v][u];
+                    weights[u][previous] = weights[previous][u];
+                }
+                weights[previous][previous] = zero;
+                let merged = ::std::mem::take(&mut groups[v]);
+                groups[previous].extend(merged);
+                active.retain(|&u| u != v);
+            }
+        }
+
+        let (weight, side) = best.unwrap();
+        let mut in_side = vec![false; n];
+        for &u in side.iter() { in_side[u] = true; }
+
+        let (inside, outside) = (0..n).partition::<Vec<usize>, _>(|&u| in_side[u]);
+        (weight,
+         inside.into_iter().map(|u| nodes[u]).collect(),
+         outside.into_iter().map(|u| nodes[u]).collect())
+    }
+}
+
+/// A [Gomory-Hu tree](http://en.wikipedia.org/wiki/Gomory%E2%80%93Hu_tree) of an undirected graph
+/// G: a weighted tree on the nodes of G where the min cut between any two nodes u and v in G
+/// equals the lightest edge on the path from u to v in the tree, and removing that edge splits
+/// the tree into the two sides of such a cut.
+///
+/// Built with Gusfield's algorithm, which needs only V - 1 max flow computations on G and no
+/// graph contractions. As with `GlobalMinCut`, every edge of G is treated as undirected.
+pub struct GomoryHuTree<N, E> {
+    tree: AdjacencyList<N, E>,
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    parent: Vec<usize>,
+    cuts: Vec<E>,
+}
+
+impl<N: Copy + Hash + Eq, E: Weight> GomoryHuTree<N, E> {
+    /// Builds the tree for a graph using the given max flow algorithm for each cut.
+    pub fn new<G, M>(graph: &G, algorithm: &M) -> Self
+        where G: Graph<NodeIndex = N, EdgeValue = E>, M: MaxFlow<AdjacencyList<(), E>>
+    {
+        let zero = E::zero();
+        let nodes = graph.nodes();
+        let n = nodes.len();
+        let index: HashMap<N, usize> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+
+        // Undirected flow network over dense indices, with both directions of each edge summed.
+        let mut weights: HashMap<(usize, usize), E> = HashMap::new();
+        for (from, to, &weight) in graph.edges().into_iter() {
+            let (u, v) = (index[&from], index[&to]);
+            if u == v { continue; }
+            for &edge in [(u, v), (v, u)].iter() {
+                let total = weights.get(&edge).map_or(weight, |&w| w + weight);
+                weights.insert(edge, total);
+            }
+        }
+
+        let mut network = AdjacencyList::new();
+        for _ in 0..n {
+            network.add_node(());
+        }
+        for ((u, v), weight) in weights.into_iter() {
+            network.add_edge(u, v, weight);
+        }
+
+        // Gusfield: node s hangs off parent[s] with the weight of the s-parent[s] min cut, and
+        // later nodes on s's side of that cut are moved under s.
+        let mut parent = vec![0; n];
+        let mut cuts = vec![zero; n];
+        for s in 1..n {
+            let t = parent[s];
+            let (source_side, _, edges) = algorithm.min_cut(&network, s, t);
+            let value = edges.into_iter().fold(zero, |acc, (_, capacity)| acc + capacity);
+            let mut in_cut = vec![false; n];
+            for &u in source_side.iter() { in_cut[u] = true; }
+
+            cuts[s] = value;
+            for i in 0..n {
+                if i != s && in_cut[i] && parent[i] == t { parent[i] = s; }
+            }
+            if in_cut[parent[t]] {
+                parent[s] = parent[t];
+                parent[t] = s;
+                cuts[s] = cuts[t];
+                cuts[t] = value;
+            }
+        }
+
+        let mut tree = AdjacencyList::new();
+        for &node in nodes.iter() {
+            tree.add_node(node);
+        }
+        for u in 0..n {
+            if parent[u] != u {
+                tree.add_edge(u, parent[u], cuts[u]);
+                tree.add_edge(parent[u], u, cuts[u]);
+            }
+        }
+
+        GomoryHuTree { tree, nodes, index, parent, cuts }
+    }
+
+    /// Returns the tree itself. Its nodes hold the corresponding nodes of the original graph (see
+    /// `tree_index` for going the other way), and each tree edge is stored in both directions.
+    pub fn tree(&self) -> &AdjacencyList<N, E> {
+        &self.tree
+    }
+
+    /// Returns the index in `tree()` of a node of the original graph.
+    pub fn tree_index(&self, node: N) -> usize {
+        self.index[&node]
+    }
+
+    /// Returns each edge of the tree once, in terms of the nodes of the original graph.
+    pub fn edges(&self) -> Vec<(N, N, E)> {
+        (0..self.nodes.len())
+            .filter(|&u| self.parent[u] != u)
+            .map(|u| (self.nodes[u], self.nodes[self.parent[u]], self.cuts[u]))
+            .collect()
+    }
+
+    /// Returns the weight of a minimum cut separating u and v in the original graph.
+    pub fn min_cut(&self, u: N, v: N) -> E {
+        let (u, v) = (self.index[&u], self.index[&v]);
+        if u == v {
+            panic!("can't cut a node from itself");
+        }
+
+        let mut adjacent: HashMap<usize, Vec<(usize, E)>> = HashMap::new();
+        for (from, to, &weight) in self.tree.edges().into_iter() {
+            adjacent.entry(from).or_default().push((to, weight));
+        }
+
+        // Walk the tree from u, tracking the lightest edge on the way to each node.
+        let mut lightest: HashMap<usize, Option<E>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        lightest.insert(u, None);
+        queue.push_back(u);
+        while let Some(x) = queue.pop_front() {
+            let so_far = lightest[&x];
+            for &(y, weight) in adjacent.get(&x).into_iter().flatten() {
+                if lightest.contains_key(&y) { continue; }
+                lightest.insert(y, Some(so_far.map_or(weight, |w| weight::min(w, weight))));
+                queue.push_back(y);
+            }
+        }
+
+        lightest.get(&v).and_then(|&w| w).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+
+    #[test]
+    fn simple() {
+        // The example from Stoer and Wagner's paper: two clusters {0, 1, 4, 5} and {2, 3, 6, 7}
+        // joined by edges of total weight 4.
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..8).map(|_| graph.add_node(())).collect();
+        let edges = vec![
+            (0, 1, 2), (0, 4, 3), (1, 2, 3), (1, 4, 2), (1, 5, 2), (2, 3, 4), (2, 6, 2),
+            (3, 6, 2), (3, 7, 2), (4, 5, 3), (5, 6, 1), (6, 7, 3),
+            ];
+        for (from, to, weight) in edges.into_iter() {
+            graph.add_edge(v[from], v[to], weight);
+        }
+
+        let (weight, mut a, mut b) = StoerWagner.global_min_cut(&graph);
+        a.sort();
+        b.sort();
+        if a.contains(&v[0]) { ::std::mem::swap(&mut a, &mut b); }
+        assert_eq!(weight, 4);
+        assert_eq!(a, vec![v[2], v[3], v[6], v[7]]);
+        assert_eq!(b, vec![v[0], v[1], v[4], v[5]]);
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 5);
+        graph.add_edge(v[2], v[3], 5);
+
+        let (weight, a, b) = StoerWagner.global_min_cut(&graph);
+        assert_eq!(weight, 0);
+        assert_eq!(a.len() + b.len(), 4);
+    }
+
+    #[test]
+    fn gomory_hu() {
+        use max_flow::Dinic;
+
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        let edges = vec![
+            (0, 1, 1), (0, 2, 7), (1, 2, 1), (1, 3, 3), (1, 4, 2), (2, 4, 4), (3, 4, 1),
+            (3, 5, 6), (4, 5, 2),
+            ];
+        for &(from, to, weight) in edges.iter() {
+            graph.add_edge(v[from], v[to], weight);
+        }
+
+        let gomory_hu = GomoryHuTree::new(&graph, &Dinic);
+        assert_eq!(gomory_hu.tree().edges().len(), 2 * (v.len() - 1));
+        assert_eq!(gomory_hu.edges().len(), v.len() - 1);
+
+        // Compare against a max flow on the same network with both directions.
+        let mut network: AdjacencyList<(), u32> = AdjacencyList::new();
+        for _ in 0..v.len() { network.add_node(()); }
+        for &(from, to, weight) in edges.iter() {
+            network.add_edge(v[from], v[to], weight);
+            network.add_edge(v[to], v[from], weight);
+        }
+        for &a in v.iter() {
+            for &b in v.iter() {
+                if a == b { continue; }
+                let (value, _) = Dinic.max_flow(&network, a, b);
+                assert_eq!(gomory_hu.min_cut(a, b), value);
+            }
+        }
+    }
+}
@@ -0,0 +1,607 @@
+//! Implements [minimum spanning tree algorithms](http://en.wikipedia.org/wiki/Minimum_spanning_tree) on graphs.
+
+use std::cmp::{max, Ordering, Reverse};
+use std::collections::{HashMap, BinaryHeap};
+use std::hash::Hash;
+use std::ops::Range;
+
+use graph::{Graph, HeapEdge};
+use indexed_heap::IndexedHeap;
+use union_find::UnionFind;
+use weight::{Ordered, Weight};
+
+pub mod sensitivity;
+
+/// Given a graph G, if G is connected, returns the total weight and the edges of an MST of G,
+/// otherwise None. Edges are treated as undirected.
+pub trait MinimumSpanningTree<G: Graph> where G::EdgeValue: Weight {
+    #[allow(clippy::type_complexity)]
+    fn minimum_spanning_tree(&self, graph: &G)
+                             -> Option<(G::EdgeValue, Vec<(G::NodeIndex, G::NodeIndex)>)>;
+}
+
+/// One tree of a minimum spanning forest, spanning a connected component of the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanningTree<N, E> {
+    /// Nodes of the component.
+    pub nodes: Vec<N>,
+    /// Total weight of the edges of the tree.
+    pub total: E,
+    pub edges: Vec<(N, N)>,
+}
+
+/// Given a graph G, returns a minimum spanning tree of each connected component of G, along with
+/// the position in that list of the tree each node belongs to. Edges are treated as undirected,
+/// and an isolated node gets a tree of its own with no edges.
+pub trait MinimumSpanningForest<G: Graph> where G::EdgeValue: Weight {
+    #[allow(clippy::type_complexity)]
+    fn minimum_spanning_forest(&self, graph: &G)
+                               -> (Vec<SpanningTree<G::NodeIndex, G::EdgeValue>>,
+                                   HashMap<G::NodeIndex, usize>);
+}
+
+/// Any spanning forest algorithm gives a spanning tree when the forest is a single tree.
+impl<G: Graph, M: MinimumSpanningForest<G>> MinimumSpanningTree<G> for M
+    where G::EdgeValue: Weight
+{
+    fn minimum_spanning_tree(&self, graph: &G)
+                             -> Option<(G::EdgeValue, Vec<(G::NodeIndex, G::NodeIndex)>)>
+    {
+        let (mut trees, _) = self.minimum_spanning_forest(graph);
+        match trees.len() {
+            0 => Some((G::EdgeValue::zero(), Vec::new())),
+            1 => trees.pop().map(|tree| (tree.total, tree.edges)),
+            _ => None,
+        }
+    }
+}
+
+// Splits the nodes into the trees formed by the chosen edges, which are given over dense node
+// indices along with their weights and how they were stored in the graph. Trees are ordered by
+// their first node.
+fn forest<N, E>(nodes: &[N], chosen: Vec<(usize, usize, E, (N, N))>)
+                -> (Vec<SpanningTree<N, E>>, HashMap<N, usize>)
+    where N: Copy + Hash + Eq, E: Weight
+{
+    let mut components = UnionFind::new(nodes.len());
+    for &(u, v, _, _) in chosen.iter() {
+        components.union(u, v);
+    }
+
+    let mut tree_of: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut trees: Vec<SpanningTree<N, E>> = Vec::new();
+    let mut membership = HashMap::new();
+    for (u, &node) in nodes.iter().enumerate() {
+        let root = components.find(u);
+        let tree = *tree_of[root].get_or_insert_with(|| {
+            trees.push(SpanningTree { nodes: Vec::new(), total: E::zero(), edges: Vec::new() });
+            trees.len() - 1
+        });
+        trees[tree].nodes.push(node);
+        membership.insert(node, tree);
+    }
+
+    for (u, _, weight, edge) in chosen.into_iter() {
+        let tree = &mut trees[tree_of[components.find(u)].unwrap()];
+        tree.total = tree.total + weight;
+        tree.edges.push(edge);
+    }
+
+    (trees, membership)
+}
+
+/// [Kruskal's algorithm](http://en.wikipedia.org/wiki/Kruskal%27s_algorithm) -- runs in
+/// O(E log E) by taking edges from lightest to heaviest, skipping any edge whose ends a
+/// union-find structure says are already connected.
+pub struct Kruskals;
+
+impl<G: Graph> MinimumSpanningForest<G> for Kruskals
+    where G::NodeIndex: Hash + Eq, G::EdgeValue: Weight
+{
+    fn minimum_spanning_forest(&self, graph: &G)
+                               -> (Vec<SpanningTree<G::NodeIndex, G::EdgeValue>>,
+                                   HashMap<G::NodeIndex, usize>)
+    {
+        let nodes = graph.nodes();
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let mut components = UnionFind::new(nodes.len());
+        let mut chosen = Vec::new();
+
+        let mut edge_heap = BinaryHeap::new();
+        for (from, to, value) in graph.edges().into_iter() {
+            edge_heap.push(HeapEdge::<G>((from, to), *value));
+        }
+
+        while components.sets() > 1 {
+            let HeapEdge((from, to), value) = match edge_heap.pop() {
+                Some(edge) => edge,
+                None => { break; }
+            };
+            let (u, v) = (index[&from], index[&to]);
+            if components.union(u, v) {
+                chosen.push((u, v, value, (from, to)));
+            }
+        }
+
+        forest(&nodes, chosen)
+    }
+}
+
+/// [Prim's algorithm](http://en.wikipedia.org/wiki/Prim%27s_algorithm) -- runs in
+/// O(E log_{E/V} V), which is O(E) on dense graphs, by growing a single tree from the lightest
+/// edge leaving it. Nodes outside the tree sit in an indexed heap keyed by their lightest edge
+/// into the tree, which is decreased in place rather than pushing every edge.
+pub struct Prims;
+
+impl<G: Graph> MinimumSpanningForest<G> for Prims
+    where G::NodeIndex: Hash + Eq, G::EdgeValue: Weight
+{
+    fn minimum_spanning_forest(&self, graph: &G)
+                               -> (Vec<SpanningTree<G::NodeIndex, G::EdgeValue>>,
+                                   HashMap<G::NodeIndex, usize>)
+    {
+        let nodes = graph.nodes();
+        let n = nodes.len();
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+
+        // Each edge goes in both adjacency lists, remembering how it was stored in the graph.
+        let graph_edges = graph.edges();
+        let mut adjacent = vec![Vec::new(); n];
+        for &(from, to, &weight) in graph_edges.iter() {
+            let (u, v) = (index[&from], index[&to]);
+            adjacent[u].push((v, weight, (from, to)));
+            adjacent[v].push((u, weight, (from, to)));
+        }
+
+        // With about E/V decreases per pop, a heap that wide balances the two.
+        let mut heap = IndexedHeap::with_arity(n, max(2, graph_edges.len() / max(n, 1)));
+        let mut lightest = vec![None; n];
+        let mut in_tree = vec![false; n];
+        let mut chosen = Vec::new();
+
+        // Grow a tree from each node that no earlier tree reached.
+        for root in 0..n {
+            if in_tree[root] { continue; }
+            heap.push(root, G::EdgeValue::zero());
+
+            while let Some((u, weight)) = heap.pop() {
+                in_tree[u] = true;
+                if let Some((parent, edge)) = lightest[u] {
+                    chosen.push((parent, u, weight, edge));
+                }
+
+                for &(v, weight, edge) in adjacent[u].iter() {
+                    if !in_tree[v] && heap.decrease_key(v, weight) {
+                        lightest[v] = Some((u, edge));
+                    }
+                }
+            }
+        }
+
+        forest(&nodes, chosen)
+    }
+}
+
+/// [Borůvka's algorithm](http://en.wikipedia.org/wiki/Bor%C5%AFvka%27s_algorithm) -- runs in
+/// O(E log V) by adding the lightest edge leaving every component at once, which at least halves
+/// the number of components each round.
+///
+/// With the `parallel` feature, each round's scan over the edges is split across one thread per
+/// available core, which is where nearly all of the time goes on large graphs.
+pub struct Boruvka;
+
+// Whether edge a is lighter than edge b. Equal weights are ordered by position, so that the
+// lightest edges of different components can never close a cycle between them.
+fn lighter<E: Weight>(edges: &[(usize, usize, E)], a: usize, b: usize) -> bool {
+    match edges[a].2.compare(&edges[b].2) {
+        Ordering::Equal => a < b,
+        ordering => ordering == Ordering::Less,
+    }
+}
+
+// Finds the lightest edge leaving each component among the given edges, indexed by the
+// representative node of the component.
+fn lightest_in<E: Weight>(edges: &[(usize, usize, E)], range: Range<usize>,
+                          component: &[usize]) -> Vec<Option<usize>> {
+    let mut lightest: Vec<Option<usize>> = vec![None; component.len()];
+    for k in range {
+        let (u, v) = (component[edges[k].0], component[edges[k].1]);
+        if u == v { continue; }
+        for &c in [u, v].iter() {
+            if lightest[c].is_none_or(|best| lighter(edges, k, best)) {
+                lightest[c] = Some(k);
+            }
+        }
+    }
+    lightest
+}
+
+#[cfg(not(feature = "parallel"))]
+fn lightest_edges<E>(edges: &[(usize, usize, E)], component: &[usize]) -> Vec<Option<usize>>
+    where E: Weight + Send + Sync
+{
+    lightest_in(edges, 0..edges.len(), component)
+}
+
+#[cfg(feature = "parallel")]
+fn lightest_edges<E>(edges: &[(usize, usize, E)], component: &[usize]) -> Vec<Option<usize>>
+    where E: Weight + Send + Sync
+{
+    use std::thread;
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = max(1, edges.len().div_ceil(threads));
+    let partial: Vec<Vec<Option<usize>>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..edges.len()).step_by(chunk)
+            .map(|start| {
+                let range = start..::std::cmp::min(start + chunk, edges.len());
+                scope.spawn(move || lightest_in(edges, range, component))
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut lightest: Vec<Option<usize>> = vec![None; component.len()];
+    for found in partial.into_iter() {
+        for (c, k) in found.into_iter().enumerate() {
+            if let Some(k) = k {
+                if lightest[c].is_none_or(|best| lighter(edges, k, best)) {
+                    lightest[c] = Some(k);
+                }
+            }
+        }
+    }
+    lightest
+}
+
+impl<G: Graph> MinimumSpanningForest<G> for Boruvka
+    where G::NodeIndex: Hash + Eq, G::EdgeValue: Weight + Send + Sync
+{
+    fn minimum_spanning_forest(&self, graph: &G)
+                               -> (Vec<SpanningTree<G::NodeIndex, G::EdgeValue>>,
+                                   HashMap<G::NodeIndex, usize>)
+    {
+        let nodes = graph.nodes();
+        let n = nodes.len();
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let graph_edges = graph.edges();
+        let dense: Vec<(usize, usize, G::EdgeValue)> = graph_edges.iter()
+            .map(|&(from, to, &weight)| (index[&from], index[&to], weight))
+            .collect();
+
+        let mut components = UnionFind::new(n);
+        let mut chosen = Vec::new();
+
+        while components.sets() > 1 {
+            let component: Vec<usize> = (0..n).map(|u| components.find(u)).collect();
+            let lightest = lightest_edges(&dense, &component);
+
+            // Once no component has an edge leaving it, each one is a tree of the forest.
+            let mut merged = false;
+            for k in lightest.into_iter().flatten() {
+                let (u, v, weight) = dense[k];
+                if components.union(u, v) {
+                    let (from, to, _) = graph_edges[k];
+                    chosen.push((u, v, weight, (from, to)));
+                    merged = true;
+                }
+            }
+            if !merged { break; }
+        }
+
+        forest(&nodes, chosen)
+    }
+}
+
+/// Iterator over every spanning tree of a connected graph in order of increasing total weight,
+/// yielding the total and the edges of each. The first tree is an MST and the second is a
+/// second-best spanning tree, so `.take(k)` gives the k best. Edges are treated as undirected.
+///
+/// Uses Lawler's partitioning, as in Gabow's algorithm: once a tree has been yielded, the trees
+/// left in its part of the search space are split into disjoint parts by forcing some of its
+/// edges in and one out. The cheapest tree of each part is the yielded tree with that one edge
+/// swapped for the lightest edge the part allows across the gap, and a single pass over the
+/// edges finds all the swaps at once, so each tree after the first costs O(E α(V)) since the
+/// edges are only sorted once.
+pub struct SpanningTrees<N, E> {
+    nodes: usize,
+    edges: Vec<(usize, usize, E, (N, N))>,
+    queue: BinaryHeap<Reverse<(Ordered<E>, Part)>>,
+    yielded: Vec<Yielded>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Part {
+    // Every spanning tree, the cheapest of which is the given MST.
+    All(Vec<usize>),
+    // The trees of a yielded tree's part that keep its first `position` free edges and rule out
+    // the next one, the cheapest of which swaps that edge for `replacement`.
+    Split { parent: usize, position: usize, replacement: usize },
+}
+
+// A tree that has been yielded. Its part forces in every edge of the tree but the free ones, and
+// rules out the excluded edge on top of everything the parent's part ruled out.
+struct Yielded {
+    tree: Vec<usize>,
+    free: Vec<usize>,
+    split: Option<(usize, usize)>,
+}
+
+impl<N: Copy + Hash + Eq, E: Weight> SpanningTrees<N, E> {
+    /// Starts enumerating the spanning trees of a graph. If the graph is disconnected there are
+    /// none.
+    pub fn new<G>(graph: &G) -> Self where G: Graph<NodeIndex = N, EdgeValue = E> {
+        let nodes = graph.nodes();
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let mut edges: Vec<_> = graph.edges().into_iter()
+            .map(|(from, to, &weight)| (index[&from], index[&to], weight, (from, to)))
+            .collect();
+        edges.sort_by(|a, b| a.2.compare(&b.2));
+
+        let mut components = UnionFind::new(nodes.len());
+        let mut total = E::zero();
+        let mut tree = Vec::new();
+        for (k, &(u, v, weight, _)) in edges.iter().enumerate() {
+            if components.union(u, v) {
+                total = total + weight;
+                tree.push(k);
+            }
+        }
+
+        let mut queue = BinaryHeap::new();
+        if components.sets() <= 1 {
+            queue.push(Reverse((Ordered(total), Part::All(tree))));
+        }
+        SpanningTrees { nodes: nodes.len(), edges, queue, yielded: Vec::new() }
+    }
+
+    // Queues the parts that the rest of a yielded tree's part splits into. Going from the
+    // lightest allowed edge to the heaviest, each one is the swap for the tree edges on its path
+    // that no lighter edge has claimed, which are skipped over with a union-find structure.
+    fn split(&mut self, id: usize, total: E) {
+        // A graph without nodes has just the one, empty, spanning tree.
+        if self.nodes == 0 { return; }
+
+        let Yielded { ref tree, ref free, split } = self.yielded[id];
+        let mut allowed = vec![true; self.edges.len()];
+        for &k in tree.iter() {
+            allowed[k] = false;
+        }
+        let mut split = split;
+        while let Some((parent, excluded)) = split {
+            allowed[excluded] = false;
+            split = self.yielded[parent].split;
+        }
+
+        // Hang the tree from node 0, noting each node's parent and the edge up to it.
+        let mut adjacent = vec![Vec::new(); self.nodes];
+        for &k in tree.iter() {
+            let (u, v, _, _) = self.edges[k];
+            adjacent[u].push((v, k));
+            adjacent[v].push((u, k));
+        }
+        let mut up: Vec<Option<(usize, usize)>> = vec![None; self.nodes];
+        let mut depth = vec![0; self.nodes];
+        let mut stack = vec![0];
+        while let Some(u) = stack.pop() {
+            for &(v, k) in adjacent[u].iter() {
+                if v != 0 && up[v].is_none() {
+                    up[v] = Some((u, k));
+                    depth[v] = depth[u] + 1;
+                    stack.push(v);
+                }
+            }
+        }
+
+        // Each node is merged into its parent once its edge up is claimed, and `highest` holds the
+        // one node of each set whose edge up is still unclaimed.
+        let mut claimed = UnionFind::new(self.nodes);
+        let mut highest: Vec<usize> = (0..self.nodes).collect();
+        let mut swaps: Vec<Option<usize>> = vec![None; self.edges.len()];
+        for k in (0..self.edges.len()).filter(|&k| allowed[k]) {
+            let (u, v, _, _) = self.edges[k];
+            let (mut u, mut v) = (highest[claimed.find(u)], highest[claimed.find(v)]);
+            while u != v {
+                if depth[u] < depth[v] {
+                    ::std::mem::swap(&mut u, &mut v);
+                }
+                let (parent, edge) = up[u].unwrap();
+                swaps[edge] = Some(k);
+                let above = highest[claimed.find(parent)];
+                claimed.union(u, parent);
+                highest[claimed.find(u)] = above;
+                u = above;
+            }
+        }
+
+        let parts: Vec<_> = free.iter().enumerate()
+            .filter_map(|(position, &k)| swaps[k].map(|replacement| {
+                let total = total - self.edges[k].2 + self.edges[replacement].2;
+                Reverse((Ordered(total), Part::Split { parent: id, position, replacement }))
+            }))
+            .collect();
+        self.queue.extend(parts);
+    }
+}
+
+impl<N: Copy + Hash + Eq, E: Weight> Iterator for SpanningTrees<N, E> {
+    type Item = (E, Vec<(N, N)>);
+
+    fn next(&mut self) -> Option<(E, Vec<(N, N)>)> {
+        let Reverse((Ordered(total), part)) = self.queue.pop()?;
+        let yielded = match part {
+            Part::All(tree) => Yielded { free: tree.clone(), tree, split: None },
+            Part::Split { parent, position, replacement } => {
+                let Yielded { ref tree, ref free, .. } = self.yielded[parent];
+                let removed = free[position];
+                Yielded {
+                    tree: tree.iter().cloned().filter(|&k| k != removed)
+                        .chain(Some(replacement)).collect(),
+                    free: free[position + 1..].iter().cloned().chain(Some(replacement)).collect(),
+                    split: Some((parent, removed)),
+                }
+            }
+        };
+
+        let id = self.yielded.len();
+        let edges = yielded.tree.iter().map(|&k| self.edges[k].3).collect();
+        self.yielded.push(yielded);
+        self.split(id, total);
+        Some((total, edges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_imports)]
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+
+    #[test]
+    fn simple() {
+        let mut graph: AdjacencyList<(), usize> = AdjacencyList::new();
+        let mut vertices = Vec::new();
+
+        for _ in 0..3 {
+            vertices.push(graph.add_node(()));
+        }
+
+        // MST is (0, 1) and (2, 0)
+        let edges = vec![((vertices[0], vertices[1]), 1usize),
+                         ((vertices[1], vertices[2]), 3),
+                         ((vertices[2], vertices[0]), 2)];
+
+        for ((from, to), weight) in edges.into_iter() {
+            graph.add_edge(from, to, weight);
+        }
+
+        let (total, mst) = Kruskals.minimum_spanning_tree(&graph).unwrap();
+        assert_eq!(total, 3);
+        assert!(mst.contains(&(vertices[0], vertices[1])));
+        assert!(mst.contains(&(vertices[2], vertices[0])));
+        assert!(!mst.contains(&(vertices[1], vertices[2])));
+    }
+
+    #[test]
+    fn joins_components() {
+        // The two cheapest edges build separate pieces, which a later edge has to join.
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..5).map(|_| graph.add_node(())).collect();
+        let edges = [(0, 1, 1), (2, 3, 1), (1, 2, 5), (0, 3, 7), (3, 4, 2), (1, 4, 9)];
+        for &(from, to, weight) in edges.iter() {
+            graph.add_edge(v[from], v[to], weight);
+        }
+
+        let (total, mst) = Kruskals.minimum_spanning_tree(&graph).unwrap();
+        assert_eq!(total, 9);
+        assert_eq!(mst.len(), 4);
+        assert!(mst.contains(&(v[1], v[2])));
+
+        let (total, mst) = Prims.minimum_spanning_tree(&graph).unwrap();
+        assert_eq!(total, 9);
+        assert!(mst.contains(&(v[1], v[2])));
+
+        let (total, _) = Boruvka.minimum_spanning_tree(&graph).unwrap();
+        assert_eq!(total, 9);
+
+        graph.add_node(());
+        assert!(Kruskals.minimum_spanning_tree(&graph).is_none());
+        assert!(Prims.minimum_spanning_tree(&graph).is_none());
+        assert!(Boruvka.minimum_spanning_tree(&graph).is_none());
+    }
+
+    #[test]
+    fn random_agreement() {
+        extern crate rand;
+
+        for _ in 0..50 {
+            let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+            let v: Vec<_> = (0..20).map(|_| graph.add_node(())).collect();
+            for i in 1..20 {
+                // A random tree keeps the graph connected.
+                graph.add_edge(v[rand::random::<usize>() % i], v[i], rand::random::<u32>() % 100);
+            }
+            for _ in 0..60 {
+                let (from, to) = (rand::random::<usize>() % 20, rand::random::<usize>() % 20);
+                graph.add_edge(v[from], v[to], rand::random::<u32>() % 100);
+            }
+
+            let (expected, _) = Kruskals.minimum_spanning_tree(&graph).unwrap();
+            for (total, mst) in [Prims.minimum_spanning_tree(&graph).unwrap(),
+                                 Boruvka.minimum_spanning_tree(&graph).unwrap()] {
+                assert_eq!(total, expected);
+                assert_eq!(mst.len(), 19);
+            }
+        }
+    }
+
+    #[test]
+    fn spanning_forest() {
+        // Two triangles and an isolated node.
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..7).map(|_| graph.add_node(())).collect();
+        let edges = [(0, 1, 4), (1, 2, 1), (2, 0, 2), (3, 4, 5), (4, 5, 3), (5, 3, 6)];
+        for &(from, to, weight) in edges.iter() {
+            graph.add_edge(v[from], v[to], weight);
+        }
+
+        let (trees, membership) = Prims.minimum_spanning_forest(&graph);
+        let mut totals: Vec<_> = trees.iter().map(|tree| (tree.nodes.len(), tree.total)).collect();
+        totals.sort();
+        assert_eq!(totals, vec![(1, 0), (3, 3), (3, 8)]);
+
+        for (t, tree) in trees.iter().enumerate() {
+            assert_eq!(tree.edges.len() + 1, tree.nodes.len());
+            for node in tree.nodes.iter() {
+                assert_eq!(membership[node], t);
+            }
+        }
+        assert_eq!(membership[&v[0]], membership[&v[2]]);
+        assert!(membership[&v[0]] != membership[&v[3]]);
+
+        let (other, _) = Kruskals.minimum_spanning_forest(&graph);
+        assert_eq!(other.len(), 3);
+        let (other, _) = Boruvka.minimum_spanning_forest(&graph);
+        assert_eq!(other.len(), 3);
+        assert!(Kruskals.minimum_spanning_tree(&graph).is_none());
+    }
+
+    #[test]
+    fn k_best() {
+        // Every spanning tree of K4 with distinct weights, checked against brute force.
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        let edges = [(0, 1, 1), (0, 2, 2), (0, 3, 4), (1, 2, 8), (1, 3, 16), (2, 3, 32)];
+        for &(from, to, weight) in edges.iter() {
+            graph.add_edge(v[from], v[to], weight);
+        }
+
+        let mut expected = Vec::new();
+        for mask in 0..(1 << edges.len()) {
+            let chosen: Vec<_> = (0..edges.len()).filter(|&k| mask & (1 << k) != 0).collect();
+            let mut components = UnionFind::new(4);
+            let spanning = chosen.iter().all(|&k| components.union(edges[k].0, edges[k].1));
+            if chosen.len() == 3 && spanning {
+                expected.push(chosen.iter().map(|&k| edges[k].2).sum::<u32>());
+            }
+        }
+        expected.sort();
+
+        let trees: Vec<_> = SpanningTrees::new(&graph).collect();
+        assert_eq!(trees.len(), 16);
+        assert_eq!(trees.iter().map(|&(total, _)| total).collect::<Vec<_>>(), expected);
+        assert_eq!(trees[0].0, Kruskals.minimum_spanning_tree(&graph).unwrap().0);
+        assert_eq!(trees[1].0, 1 + 4 + 8);
+
+        graph.add_node(());
+        assert!(SpanningTrees::new(&graph).next().is_none());
+    }
+
+    #[test]
+    fn no_nodes() {
+        let graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let trees: Vec<_> = SpanningTrees::new(&graph).collect();
+        assert_eq!(trees, vec![(0, vec![])]);
+        assert_eq!(Kruskals.minimum_spanning_tree(&graph), Some((0, vec![])));
+    }
+}
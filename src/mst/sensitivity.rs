@@ -0,0 +1,330 @@
+//! Implements verification and sensitivity analysis of minimum spanning trees: checking that a
+//! given spanning tree is minimum, and how far the weight of each edge can move before it isn't.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use graph::Graph;
+use union_find::UnionFind;
+use weight::{self, Weight};
+
+/// Why a claimed tree isn't a minimum spanning tree of a graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidTree<N> {
+    /// The edge isn't in the graph in either direction.
+    UnknownEdge((N, N)),
+    /// The edges have a cycle or don't reach every node of the graph.
+    NotSpanning,
+    /// The edge is lighter than the tree edge it replaces on the cycle it closes in the tree, so
+    /// swapping the two gives a lighter spanning tree.
+    NotMinimum { edge: (N, N), replaces: (N, N) },
+}
+
+/// How an edge relates to a minimum spanning tree, which explains why it is or isn't in the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeSensitivity<N, E> {
+    pub edge: (N, N),
+    pub weight: E,
+    pub in_tree: bool,
+    /// For a tree edge, the lightest edge that can take its place, which crosses the cut the tree
+    /// edge makes. For any other edge, the heaviest edge on the path between its ends in the tree,
+    /// which it would take the place of. None if there is no such edge.
+    pub replacement: Option<(N, N)>,
+    /// How much the weight of a tree edge can increase, or the weight of any other edge can
+    /// decrease, with the tree staying minimum: the difference between the weights of the edge and
+    /// its replacement. None if it can move without limit.
+    pub slack: Option<E>,
+}
+
+// A spanning tree hung from node 0, with jump pointers 2^j levels up along with the heaviest edge
+// on the way, so that the heaviest edge on any path takes O(log V).
+struct RootedTree {
+    parent: Vec<usize>,
+    parent_edge: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    up: Vec<Vec<usize>>,
+    heaviest: Vec<Vec<Option<usize>>>,
+}
+
+fn heavier<E: Weight>(weights: &[E], a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) if weights[b].compare(&weights[a]) == Ordering::Greater => Some(b),
+        (None, b) => b,
+        (a, _) => a,
+    }
+}
+
+impl RootedTree {
+    fn new<E: Weight>(n: usize, tree: &[(usize, usize, usize)], weights: &[E]) -> Self {
+        let mut adjacent = vec![Vec::new(); n];
+        for &(u, v, k) in tree.iter() {
+            adjacent[u].push((v, k));
+            adjacent[v].push((u, k));
+        }
+
+        let mut parent: Vec<usize> = (0..n).collect();
+        let mut parent_edge = vec![None; n];
+        let mut depth = vec![0; n];
+        let mut visited = vec![false; n];
+        let mut queue = VecDeque::new();
+        if n > 0 {
+            visited[0] = true;
+            queue.push_back(0);
+        }
+        while let Some(u) = queue.pop_front() {
+            for &(v, k) in adjacent[u].iter() {
+                if visited[v] { continue; }
+                visited[v] = true;
+                parent[v] = u;
+                parent_edge[v] = Some(k);
+                depth[v] = depth[u] + 1;
+                queue.push_back(v);
+            }
+        }
+
+        let mut up = vec![parent.clone()];
+        let mut heaviest = vec![parent_edge.clone()];
+        while 1 << up.len() < n {
+            let (last_up, last_heaviest) = (up.last().unwrap(), heaviest.last().unwrap());
+            let next_up = (0..n).map(|v| last_up[last_up[v]]).collect();
+            let next_heaviest = (0..n)
+                .map(|v| heavier(weights, last_heaviest[v], last_heaviest[last_up[v]]))
+                .collect();
+            up.push(next_up);
+            heaviest.push(next_heaviest);
+        }
+
+        RootedTree { parent, parent_edge, depth, up, heaviest }
+    }
+
+    // The heaviest edge on the tree path between u and v.
+    fn heaviest_on_path<E: Weight>(&self, weights: &[E], mut u: usize, mut v: usize)
+                                   -> Option<usize> {
+        let mut found = None;
+        if self.depth[u] < self.depth[v] {
+            ::std::mem::swap(&mut u, &mut v);
+        }
+        for j in (0..self.up.len()).rev() {
+            if self.depth[u] - self.depth[v] >= 1 << j {
+                found = heavier(weights, found, self.heaviest[j][u]);
+                u = self.up[j][u];
+            }
+        }
+        if u == v {
+            return found;
+        }
+
+        for j in (0..self.up.len()).rev() {
+            if self.up[j][u] != self.up[j][v] {
+                found = heavier(weights, found, self.heaviest[j][u]);
+                found = heavier(weights, found, self.heaviest[j][v]);
+                u = self.up[j][u];
+                v = self.up[j][v];
+            }
+        }
+        let found = heavier(weights, found, self.parent_edge[u]);
+        heavier(weights, found, self.parent_edge[v])
+    }
+}
+
+/// Given a graph G and the edges of a spanning tree of G, such as the output of a
+/// `MinimumSpanningTree`, returns how every edge of G relates to the tree, or why the tree isn't
+/// a minimum spanning tree of G. Edges are treated as undirected, so tree edges may be given in
+/// either direction.
+///
+/// The tree is minimum exactly when no other edge is lighter than the heaviest tree edge on the
+/// cycle it closes, which is found for all edges at once in O(E log V). The replacements of the
+/// tree edges come from the other edges in order of weight, each claiming the tree edges on its
+/// cycle that no lighter edge has claimed yet, skipping past claimed ones with a union-find
+/// structure.
+#[allow(clippy::type_complexity)]
+pub fn sensitivity<G: Graph>(graph: &G, tree: &[(G::NodeIndex, G::NodeIndex)])
+                             -> Result<Vec<EdgeSensitivity<G::NodeIndex, G::EdgeValue>>,
+                                       InvalidTree<G::NodeIndex>>
+    where G::NodeIndex: Hash + Eq, G::EdgeValue: Weight
+{
+    let nodes = graph.nodes();
+    let n = nodes.len();
+    let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+    let graph_edges = graph.edges();
+    let edges: Vec<_> = graph_edges.iter()
+        .map(|&(from, to, _)| (index[&from], index[&to], (from, to)))
+        .collect();
+    let weights: Vec<G::EdgeValue> = graph_edges.iter().map(|&(_, _, &weight)| weight).collect();
+    let position: HashMap<_, _> = edges.iter().enumerate()
+        .map(|(k, &(_, _, edge))| (edge, k))
+        .collect();
+
+    let mut in_tree = vec![false; edges.len()];
+    let mut tree_edges = Vec::new();
+    let mut components = UnionFind::new(n);
+    for &(from, to) in tree.iter() {
+        let k = match position.get(&(from, to)).or_else(|| position.get(&(to, from))) {
+            Some(&k) => k,
+            None => return Err(InvalidTree::UnknownEdge((from, to))),
+        };
+        let (u, v, _) = edges[k];
+        if !components.union(u, v) {
+            return Err(InvalidTree::NotSpanning);
+        }
+        in_tree[k] = true;
+        tree_edges.push((u, v, k));
+    }
+    if components.sets() > 1 {
+        return Err(InvalidTree::NotSpanning);
+    }
+
+    let rooted = RootedTree::new(n, &tree_edges, &weights);
+    let mut replacement: Vec<Option<usize>> = vec![None; edges.len()];
+    for (k, &(u, v, edge)) in edges.iter().enumerate() {
+        if in_tree[k] { continue; }
+        replacement[k] = rooted.heaviest_on_path(&weights, u, v);
+        if let Some(h) = replacement[k] {
+            if weights[k].less_than(weights[h]) {
+                return Err(InvalidTree::NotMinimum { edge, replaces: edges[h].2 });
+            }
+        }
+    }
+
+    // Every node points up at the highest node it can reach through claimed tree edges.
+    let mut others: Vec<usize> = (0..edges.len()).filter(|&k| !in_tree[k]).collect();
+    others.sort_by(|&a, &b| weights[a].compare(&weights[b]));
+    let mut claimed = UnionFind::new(n);
+    let mut top: Vec<usize> = (0..n).collect();
+    for &k in others.iter() {
+        let (u, v, _) = edges[k];
+        let (mut u, mut v) = (top[claimed.find(u)], top[claimed.find(v)]);
+        while u != v {
+            if rooted.depth[u] < rooted.depth[v] {
+                ::std::mem::swap(&mut u, &mut v);
+            }
+            replacement[rooted.parent_edge[u].unwrap()] = Some(k);
+            let above = top[claimed.find(rooted.parent[u])];
+            claimed.union(u, rooted.parent[u]);
+            top[claimed.find(u)] = above;
+            u = above;
+        }
+    }
+
+    Ok(edges.iter().enumerate().map(|(k, &(_, _, edge))| {
+        let weight = weights[k];
+        let slack = replacement[k].map(|r| {
+            let difference = if in_tree[k] { weights[r] - weight } else { weight - weights[r] };
+            weight::max(difference, G::EdgeValue::zero())
+        });
+        EdgeSensitivity {
+            edge,
+            weight,
+            in_tree: in_tree[k],
+            replacement: replacement[k].map(|r| edges[r].2),
+            slack,
+        }
+    }).collect())
+}
+
+/// Given a graph G and the edges of a spanning tree of G, checks that the tree is a minimum
+/// spanning tree of G, in the same way as `sensitivity` but without finding replacements for the
+/// tree edges.
+pub fn verify<G: Graph>(graph: &G, tree: &[(G::NodeIndex, G::NodeIndex)])
+                        -> Result<(), InvalidTree<G::NodeIndex>>
+    where G::NodeIndex: Hash + Eq, G::EdgeValue: Weight
+{
+    sensitivity(graph, tree).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+    use mst::{Kruskals, MinimumSpanningTree};
+
+    #[test]
+    fn simple() {
+        // MST is (0, 1), (1, 2) and (2, 3) for a total of 6.
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        let edges = [(0, 1, 1), (1, 2, 2), (2, 3, 3), (0, 2, 4), (1, 3, 7)];
+        for &(from, to, weight) in edges.iter() {
+            graph.add_edge(v[from], v[to], weight);
+        }
+
+        let (_, mut tree) = Kruskals.minimum_spanning_tree(&graph).unwrap();
+        let report: HashMap<_, _> = sensitivity(&graph, &tree).unwrap().into_iter()
+            .map(|edge| (edge.edge, (edge.in_tree, edge.replacement, edge.slack)))
+            .collect();
+
+        assert_eq!(report[&(v[0], v[1])], (true, Some((v[0], v[2])), Some(3)));
+        assert_eq!(report[&(v[1], v[2])], (true, Some((v[0], v[2])), Some(2)));
+        assert_eq!(report[&(v[2], v[3])], (true, Some((v[1], v[3])), Some(4)));
+        assert_eq!(report[&(v[0], v[2])], (false, Some((v[1], v[2])), Some(2)));
+        assert_eq!(report[&(v[1], v[3])], (false, Some((v[2], v[3])), Some(4)));
+
+        // A bridge can get as heavy as it likes.
+        let w = graph.add_node(());
+        graph.add_edge(v[3], w, 9);
+        tree.push((w, v[3]));
+        let bridge = sensitivity(&graph, &tree).unwrap().into_iter()
+            .find(|edge| edge.edge == (v[3], w))
+            .unwrap();
+        assert_eq!((bridge.in_tree, bridge.replacement, bridge.slack), (true, None, None));
+    }
+
+    #[test]
+    fn invalid() {
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+        graph.add_edge(v[0], v[1], 1);
+        graph.add_edge(v[1], v[2], 5);
+        graph.add_edge(v[2], v[0], 2);
+
+        assert_eq!(verify(&graph, &[(v[1], v[0]), (v[0], v[2])]), Ok(()));
+        assert_eq!(verify(&graph, &[(v[0], v[1]), (v[1], v[2])]),
+                   Err(InvalidTree::NotMinimum { edge: (v[2], v[0]), replaces: (v[1], v[2]) }));
+        assert_eq!(verify(&graph, &[(v[0], v[1])]), Err(InvalidTree::NotSpanning));
+        assert_eq!(verify(&graph, &[(v[0], v[1]), (v[1], v[0])]), Err(InvalidTree::NotSpanning));
+        assert_eq!(verify(&graph, &[(v[0], v[1]), (v[0], v[0])]),
+                   Err(InvalidTree::UnknownEdge((v[0], v[0]))));
+    }
+
+    #[test]
+    fn random() {
+        // Moving an edge's weight by its slack keeps the tree minimum, and moving it any further
+        // doesn't.
+        for _ in 0..30 {
+            let n = 8;
+            let mut graph: AdjacencyList<(), i32> = AdjacencyList::new();
+            for _ in 0..n { graph.add_node(()); }
+            for u in 1..n {
+                graph.add_edge(rand::random::<usize>() % u, u, rand::random::<i32>() % 20);
+            }
+            for _ in 0..12 {
+                let (from, to) = (rand::random::<usize>() % n, rand::random::<usize>() % n);
+                graph.add_edge(from, to, rand::random::<i32>() % 20);
+            }
+
+            let (_, tree) = Kruskals.minimum_spanning_tree(&graph).unwrap();
+            for edge in sensitivity(&graph, &tree).unwrap().into_iter() {
+                let (from, to) = edge.edge;
+                let direction = if edge.in_tree { 1 } else { -1 };
+                let slack = match edge.slack {
+                    Some(slack) => slack,
+                    None => {
+                        graph.add_edge(from, to, edge.weight + direction * 100);
+                        assert_eq!(verify(&graph, &tree), Ok(()));
+                        graph.add_edge(from, to, edge.weight);
+                        continue;
+                    }
+                };
+
+                graph.add_edge(from, to, edge.weight + direction * slack);
+                assert_eq!(verify(&graph, &tree), Ok(()));
+                graph.add_edge(from, to, edge.weight + direction * (slack + 1));
+                assert!(verify(&graph, &tree).is_err());
+                graph.add_edge(from, to, edge.weight);
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Implements approximations of the
+//! [Steiner tree problem](http://en.wikipedia.org/wiki/Steiner_tree_problem): connecting a given
+//! set of terminal nodes as cheaply as possible, optionally through other nodes of the graph.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use graph::{AdjacencyList, Graph};
+use indexed_heap::IndexedHeap;
+use mst::MinimumSpanningTree;
+use weight::Weight;
+
+/// Given a graph G with non-negative weights on the edges and a set of terminal nodes, returns the
+/// total weight and the edges of a tree connecting all of the terminals, or None if some of them
+/// aren't connected. Edges are treated as undirected, and the spanning trees along the way are
+/// found with the given MST algorithm.
+pub trait SteinerTree<G: Graph> where G::EdgeValue: Weight {
+    #[allow(clippy::type_complexity)]
+    fn steiner_tree<M>(&self, graph: &G, terminals: &[G::NodeIndex], mst: &M)
+                       -> Option<(G::EdgeValue, Vec<(G::NodeIndex, G::NodeIndex)>)>
+        where M: MinimumSpanningTree<AdjacencyList<(), G::EdgeValue>>;
+}
+
+/// The [metric closure](http://en.wikipedia.org/wiki/Metric_closure) 2-approximation of Kou,
+/// Markowsky and Berman -- runs in O(T E log V) for T terminals. Takes an MST of the complete
+/// graph on the terminals weighted by their distances, and replaces each of its edges by a
+/// shortest path.
+pub struct MetricClosure;
+
+/// Mehlhorn's variant of the metric closure -- runs in O(E log V) and is also a 2-approximation.
+/// A single shortest path search from all terminals at once splits the graph into regions around
+/// their nearest terminal, and only terminals whose regions touch get an edge, weighted by the
+/// shortest path through the edge where they touch.
+pub struct Mehlhorn;
+
+// The graph over dense node indices, with each edge in both adjacency lists.
+struct Network<N, E> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    edges: Vec<(usize, usize, E, (N, N))>,
+    adjacent: Vec<Vec<(usize, usize)>>,
+}
+
+// Shortest paths from the nearest of the sources to every node: its distance, the edge towards
+// that source, and which source it is.
+struct ShortestPaths<E> {
+    distance: Vec<Option<E>>,
+    parent: Vec<Option<usize>>,
+    nearest: Vec<usize>,
+}
+
+impl<N: Copy + Hash + Eq, E: Weight> Network<N, E> {
+    fn new<G>(graph: &G) -> Self where G: Graph<NodeIndex = N, EdgeValue = E> {
+        let nodes = graph.nodes();
+        let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let edges: Vec<_> = graph.edges().into_iter()
+            .map(|(from, to, &weight)| (index[&from], index[&to], weight, (from, to)))
+            .collect();
+
+        let mut adjacent = vec![Vec::new(); nodes.len()];
+        for (k, &(u, v, _, _)) in edges.iter().enumerate() {
+            adjacent[u].push((v, k));
+            adjacent[v].push((u, k));
+        }
+
+        Network { nodes, index, edges, adjacent }
+    }
+
+    // Dijkstra's algorithm started from all of the sources at once.
+    fn shortest_paths(&self, sources: &[usize]) -> ShortestPaths<E> {
+        let n = self.nodes.len();
+        let mut paths = ShortestPaths {
+            distance: vec![None; n],
+            parent: vec![None; n],
+            nearest: vec![0; n],
+        };
+        let mut heap = IndexedHeap::new(n);
+        for &s in sources.iter() {
+            heap.push(s, E::zero());
+            paths.nearest[s] = s;
+        }
+
+        while let Some((u, distance)) = heap.pop() {
+            paths.distance[u] = Some(distance);
+            for &(v, k) in self.adjacent[u].iter() {
+                if paths.distance[v].is_none()
+                    && heap.decrease_key(v, distance.saturating_add(self.edges[k].2)) {
+                    paths.parent[v] = Some(k);
+                    paths.nearest[v] = paths.nearest[u];
+                }
+            }
+        }
+
+        paths
+    }
+
+    // Adds the edges on the shortest path from u back to its source.
+    fn path_edges(&self, paths: &ShortestPaths<E>, mut u: usize, used: &mut Vec<usize>) {
+        while let Some(k) = paths.parent[u] {
+            used.push(k);
+            let (a, b, _, _) = self.edges[k];
+            u = if a == u { b } else { a };
+        }
+    }
+
+    // Turns the union of the given edges into a tree through the terminals: takes an MST of it,
+    // then repeatedly drops leaves that aren't terminals.
+    fn connect<M>(&self, mut used: Vec<usize>, terminals: &[usize], mst: &M) -> (E, Vec<(N, N)>)
+        where M: MinimumSpanningTree<AdjacencyList<(), E>>
+    {
+        used.sort();
+        used.dedup();
+
+        let mut subgraph = AdjacencyList::new();
+        let mut local = HashMap::new();
+        let mut position = HashMap::new();
+        for &k in used.iter() {
+            let (u, v, weight, _) = self.edges[k];
+            let from = *local.entry(u).or_insert_with(|| subgraph.add_node(()));
+            let to = *local.entry(v).or_insert_with(|| subgraph.add_node(()));
+            subgraph.add_edge(from, to, weight);
+            position.insert((from, to), k);
+        }
+
+        let (_, tree) = mst.minimum_spanning_tree(&subgraph).unwrap();
+        let mut tree: Vec<usize> = tree.into_iter().map(|edge| position[&edge]).collect();
+
+        let mut degree = vec![0; self.nodes.len()];
+        let mut keep = vec![false; self.nodes.len()];
+        for &t in terminals.iter() { keep[t] = true; }
+        for &k in tree.iter() {
+            degree[self.edges[k].0] += 1;
+            degree[self.edges[k].1] += 1;
+        }
+        loop {
+            let before = tree.len();
+            tree.retain(|&k| {
+                let (u, v, _, _) = self.edges[k];
+                let leaf = (degree[u] == 1 && !keep[u]) || (degree[v] == 1 && !keep[v]);
+                if leaf {
+                    degree[u] -= 1;
+                    degree[v] -= 1;
+                }
+                !leaf
+            });
+            if tree.len() == before { break; }
+        }
+
+        let total = tree.iter().fold(E::zero(), |acc, &k| acc + self.edges[k].2);
+        (total, tree.into_iter().map(|k| self.edges[k].3).collect())
+    }
+
+    fn terminals(&self, terminals: &[N]) -> Vec<usize> {
+        let mut terminals: Vec<usize> = terminals.iter().map(|t| self.index[t]).collect();
+        terminals.sort();
+        terminals.dedup();
+        terminals
+    }
+}
+
+impl<G: Graph> SteinerTree<G> for MetricClosure
+    where G::NodeIndex: Hash + Eq, G::EdgeValue: Weight
+{
+    fn steiner_tree<M>(&self, graph: &G, terminals: &[G::NodeIndex], mst: &M)
+                       -> Option<(G::EdgeValue, Vec<(G::NodeIndex, G::NodeIndex)>)>
+        where M: MinimumSpanningTree<AdjacencyList<(), G::EdgeValue>>
+    {
+        let network = Network::new(graph);
+        let terminals = network.terminals(terminals);
+
+        // Terminal i is node i of the closure.
+        let mut closure = AdjacencyList::new();
+        for _ in terminals.iter() { closure.add_node(()); }
+        let paths: Vec<_> = terminals.iter().map(|&t| network.shortest_paths(&[t])).collect();
+        for (i, from) in paths.iter().enumerate() {
+            for (j, &to) in terminals.iter().enumerate().skip(i + 1) {
+                if let Some(distance) = from.distance[to] {
+                    closure.add_edge(i, j, distance);
+                }
+            }
+        }
+
+        let (_, tree) = mst.minimum_spanning_tree(&closure)?;
+        let mut used = Vec::new();
+        for (i, j) in tree.into_iter() {
+            network.path_edges(&paths[i], terminals[j], &mut used);
+        }
+        Some(network.connect(used, &terminals, mst))
+    }
+}
+
+impl<G: Graph> SteinerTree<G> for Mehlhorn
+    where G::NodeIndex: Hash + Eq, G::EdgeValue: Weight
+{
+    fn steiner_tree<M>(&self, graph: &G, terminals: &[G::NodeIndex], mst: &M)
+                       -> Option<(G::EdgeValue, Vec<(G::NodeIndex, G::NodeIndex)>)>
+        where M: MinimumSpanningTree<AdjacencyList<(), G::EdgeValue>>
+    {
+        let network = Network::new(graph);
+        let terminals = network.terminals(terminals);
+        let paths = network.shortest_paths(&terminals);
+
+        // The lightest path between each pair of neighbouring regions, and the edge it crosses.
+        let mut bridges: HashMap<(usize, usize), (G::EdgeValue, usize)> = HashMap::new();
+        for (k, &(u, v, weight, _)) in network.edges.iter().enumerate() {
+            let (a, b) = (paths.nearest[u], paths.nearest[v]);
+            if a == b { continue; }
+            let (du, dv) = match (paths.distance[u], paths.distance[v]) {
+                (Some(du), Some(dv)) => (du, dv),
+                _ => continue,
+            };
+            let length = du.saturating_add(weight).saturating_add(dv);
+            let pair = if a < b { (a, b) } else { (b, a) };
+            if bridges.get(&pair).is_none_or(|&(best, _)| length.less_than(best)) {
+                bridges.insert(pair, (length, k));
+            }
+        }
+
+        let mut closure = AdjacencyList::new();
+        let local: HashMap<usize, usize> = terminals.iter()
+            .map(|&t| (t, closure.add_node(())))
+            .collect();
+        for (&(a, b), &(length, _)) in bridges.iter() {
+            closure.add_edge(local[&a], local[&b], length);
+        }
+
+        let (_, tree) = mst.minimum_spanning_tree(&closure)?;
+        let mut used = Vec::new();
+        for (i, j) in tree.into_iter() {
+            let (_, k) = bridges[&(terminals[i], terminals[j])];
+            let (u, v, _, _) = network.edges[k];
+            used.push(k);
+            network.path_edges(&paths, u, &mut used);
+            network.path_edges(&paths, v, &mut used);
+        }
+        Some(network.connect(used, &terminals, mst))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::*;
+    use graph::{AdjacencyList, Graph};
+    use mst::{Kruskals, Prims};
+    use union_find::UnionFind;
+
+    // Checks that the edges form a tree through all of the terminals with the given total.
+    fn check_tree(graph: &AdjacencyList<(), u32>, terminals: &[usize], total: u32,
+                  tree: &[(usize, usize)]) {
+        let weights: HashMap<_, _> = graph.edges().into_iter()
+            .map(|(from, to, &weight)| ((from, to), weight))
+            .collect();
+        let mut components = UnionFind::new(graph.nodes().len());
+        for &(from, to) in tree.iter() {
+            assert!(components.union(from, to));
+        }
+        assert!(terminals.iter().all(|&t| components.same(t, terminals[0])));
+        assert_eq!(tree.iter().map(|edge| weights[edge]).sum::<u32>(), total);
+    }
+
+    #[test]
+    fn simple() {
+        // Three terminals around a hub: the hub connects them for 3, going around costs 6.
+        let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+        let v: Vec<_> = (0..5).map(|_| graph.add_node(())).collect();
+        let edges = [(0, 1, 3), (1, 2, 3), (2, 0, 3), (3, 0, 1), (3, 1, 1), (3, 2, 1), (4, 0, 5)];
+        for &(from, to, weight) in edges.iter() {
+            graph.add_edge(v[from], v[to], weight);
+        }
+
+        let terminals = [v[0], v[1], v[2]];
+        let star = vec![(v[3], v[0]), (v[3], v[1]), (v[3], v[2])];
+        let (total, mut tree) = MetricClosure.steiner_tree(&graph, &terminals, &Prims).unwrap();
+        tree.sort();
+        assert_eq!((total, tree), (3, star.clone()));
+        let (total, mut tree) = Mehlhorn.steiner_tree(&graph, &terminals, &Kruskals).unwrap();
+        tree.sort();
+        assert_eq!((total, tree), (3, star));
+
+        assert_eq!(MetricClosure.steiner_tree(&graph, &[v[4]], &Kruskals), Some((0, vec![])));
+        graph.add_node(());
+        assert_eq!(Mehlhorn.steiner_tree(&graph, &[v[0], 5], &Kruskals), None);
+    }
+
+    #[test]
+    fn random() {
+        // Compare against the MSTs of every set of nodes containing the terminals.
+        for _ in 0..50 {
+            let n = 8;
+            let mut graph: AdjacencyList<(), u32> = AdjacencyList::new();
+            for _ in 0..n { graph.add_node(()); }
+            for u in 1..n {
+                graph.add_edge(rand::random::<usize>() % u, u, rand::random::<u32>() % 10 + 1);
+            }
+            for _ in 0..8 {
+                let (from, to) = (rand::random::<usize>() % n, rand::random::<usize>() % n);
+                graph.add_edge(from, to, rand::random::<u32>() % 10 + 1);
+            }
+            let terminals: Vec<usize> = (0..n).filter(|_| rand::random::<bool>()).collect();
+
+            let mut best = None;
+            for subset in 0..(1 << n) {
+                if terminals.iter().any(|&t| subset & (1 << t) == 0) { continue; }
+                let mut induced: AdjacencyList<(), u32> = AdjacencyList::new();
+                let local: HashMap<usize, usize> = (0..n).filter(|&u| subset & (1 << u) != 0)
+                    .map(|u| (u, induced.add_node(())))
+                    .collect();
+                for (from, to, &weight) in graph.edges().into_iter() {
+                    if local.contains_key(&from) && local.contains_key(&to) {
+                        induced.add_edge(local[&from], local[&to], weight);
+                    }
+                }
+                if let Some((total, _)) = Kruskals.minimum_spanning_tree(&induced) {
+                    best = Some(best.map_or(total, |b| ::std::cmp::min(b, total)));
+                }
+            }
+            let best = best.unwrap();
+
+            for &(total, ref tree) in [
+                MetricClosure.steiner_tree(&graph, &terminals, &Kruskals).unwrap(),
+                Mehlhorn.steiner_tree(&graph, &terminals, &Prims).unwrap(),
+            ].iter() {
+                if !terminals.is_empty() { check_tree(&graph, &terminals, total, tree); }
+                assert!(best <= total && total <= 2 * best);
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Implements a [union-find](http://en.wikipedia.org/wiki/Disjoint-set_data_structure) structure
+//! over the elements 0 to n - 1.
+
+/// Disjoint sets with union by rank and path compression, so that any sequence of operations runs
+/// in nearly linear time (O(m α(n)) for m operations).
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Creates n singleton sets, one for each element from 0 to n - 1.
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            sets: n,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// Returns the representative of the set containing x, which is the same for every element
+    /// of the set until it is merged with another one.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Point everything on the way straight at the root.
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing x and y. Returns false if they were already the same set.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (x, y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+
+        let (low, high) = if self.rank[x] < self.rank[y] { (x, y) } else { (y, x) };
+        self.parent[low] = high;
+        if self.rank[low] == self.rank[high] {
+            self.rank[high] += 1;
+        }
+        self.sets -= 1;
+        true
+    }
+
+    /// Returns whether x and y are in the same set.
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.sets(), 6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert_eq!(sets.sets(), 3);
+
+        assert!(sets.same(0, 3));
+        assert!(!sets.same(0, 4));
+        assert_eq!(sets.find(2), sets.find(1));
+        assert!(sets.find(4) != sets.find(5));
+    }
+
+    #[test]
+    fn chain() {
+        // A long chain of unions still ends up in a single flat set.
+        let n = 1000;
+        let mut sets = UnionFind::new(n);
+        for x in 1..n {
+            sets.union(x - 1, x);
+        }
+        assert_eq!(sets.sets(), 1);
+
+        let root = sets.find(0);
+        for x in 0..n {
+            assert_eq!(sets.find(x), root);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Numeric edge weights shared by the graph algorithms, whether they're capacities in a flow
+//! network, costs of a spanning tree or lengths of a path.
+
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+
+/// Numeric operations needed on edge weights. Implemented for the primitive integers, which are
+/// compared exactly, and for `f32` and `f64`, where values within a relative `epsilon()` of each
+/// other count as equal so that rounding errors can't leave an algorithm chasing tiny leftovers
+/// forever. Checks against zero take a scale, the size of the weights the value was computed
+/// from, since zero on its own has no size to be relative to.
+pub trait Weight: Copy + PartialOrd + Default + Add<Output = Self> + Sub<Output = Self> {
+    /// Same as `Default::default()`.
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Largest difference between two values that are still considered equal, as a fraction of
+    /// the larger of the two.
+    fn epsilon() -> Self {
+        Self::zero()
+    }
+
+    /// Largest difference from this weight that is still considered equal to it, which is
+    /// `epsilon()` times its magnitude, or zero if it is infinite.
+    fn tolerance(self) -> Self {
+        Self::zero()
+    }
+
+    /// Largest representable value, which stands in for an unbounded weight.
+    fn infinity() -> Self;
+
+    /// Adds two weights, stopping at the largest or smallest value instead of overflowing.
+    fn saturating_add(self, other: Self) -> Self;
+
+    /// Subtracts two weights, stopping at the largest or smallest value instead of overflowing.
+    fn saturating_sub(self, other: Self) -> Self;
+
+    /// Exact total order on weights, for sorting and heaps. Floating point NaNs go last.
+    fn compare(&self, other: &Self) -> Ordering;
+
+    /// Whether the weight is more than `scale.tolerance()` above zero.
+    fn is_above_zero(self, scale: Self) -> bool {
+        self > scale.tolerance()
+    }
+
+    /// Whether the weight is more than `scale.tolerance()` below zero.
+    fn is_below_zero(self, scale: Self) -> bool {
+        self + scale.tolerance() < Self::zero()
+    }
+
+    /// Whether the weight is within `scale.tolerance()` of zero.
+    fn is_zero(self, scale: Self) -> bool {
+        !self.is_above_zero(scale) && !self.is_below_zero(scale)
+    }
+
+    /// Whether the weight is smaller than `other` by more than the tolerance of either.
+    fn less_than(self, other: Self) -> bool {
+        let (a, b) = (self.tolerance(), other.tolerance());
+        self + (if a < b { b } else { a }) < other
+    }
+}
+
+macro_rules! integer_weight {
+    ($($t:ty),*) => {$(
+        impl Weight for $t {
+            fn infinity() -> $t { <$t>::MAX }
+            fn saturating_add(self, other: $t) -> $t { <$t>::saturating_add(self, other) }
+            fn saturating_sub(self, other: $t) -> $t { <$t>::saturating_sub(self, other) }
+            fn compare(&self, other: &$t) -> Ordering { Ord::cmp(self, other) }
+        }
+    )*}
+}
+
+macro_rules! float_weight {
+    ($($t:ty, $epsilon:expr);*) => {$(
+        impl Weight for $t {
+            fn epsilon() -> $t { $epsilon }
+            fn tolerance(self) -> $t {
+                if self.is_finite() { self.abs() * $epsilon } else { 0.0 }
+            }
+            fn infinity() -> $t { <$t>::INFINITY }
+            fn saturating_add(self, other: $t) -> $t { self + other }
+            fn saturating_sub(self, other: $t) -> $t { self - other }
+            fn compare(&self, other: &$t) -> Ordering {
+                match (self.is_nan(), other.is_nan()) {
+                    (false, false) => self.partial_cmp(other).unwrap(),
+                    (me, other) => me.cmp(&other),
+                }
+            }
+        }
+    )*}
+}
+
+integer_weight!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+float_weight!(f32, 1e-5; f64, 1e-9);
+
+/// Returns the smaller of two weights, or the first if they're equal.
+pub fn min<W: Weight>(a: W, b: W) -> W {
+    if b.compare(&a) == Ordering::Less { b } else { a }
+}
+
+/// Returns the larger of two weights, or the second if they're equal.
+pub fn max<W: Weight>(a: W, b: W) -> W {
+    if b.compare(&a) == Ordering::Less { a } else { b }
+}
+
+/// Wraps a weight to give it the total order of `Weight::compare`, so it can go in a
+/// `BinaryHeap` or be used as a sort key.
+#[derive(Debug, Clone, Copy)]
+pub struct Ordered<W>(pub W);
+
+impl<W: Weight> PartialEq for Ordered<W> {
+    fn eq(&self, other: &Ordered<W>) -> bool {
+        self.0.compare(&other.0) == Ordering::Equal
+    }
+}
+
+impl<W: Weight> Eq for Ordered<W> {}
+
+impl<W: Weight> PartialOrd for Ordered<W> {
+    fn partial_cmp(&self, other: &Ordered<W>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> Ord for Ordered<W> {
+    fn cmp(&self, other: &Ordered<W>) -> Ordering {
+        self.0.compare(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert!(1u32.is_above_zero(5) && 0u32.is_zero(5) && (-1i64).is_below_zero(5));
+        assert!(1u64.less_than(2) && !2u64.less_than(2));
+        assert_eq!(Weight::saturating_add(u32::infinity(), 1), u32::MAX);
+        assert_eq!(Weight::saturating_sub(0u32, 1), 0);
+        assert_eq!(min(3i64, -4), -4);
+        assert_eq!(max(3i64, -4), 3);
+    }
+
+    #[test]
+    fn floats() {
+        let third = 1.0 / 3.0;
+        assert!((third + third + third - 1.0f64).is_zero(1.0));
+        assert!(1e-12f64.is_zero(1.0) && 1e-3f64.is_above_zero(1.0));
+        assert!((-1e-3f64).is_below_zero(1.0));
+        assert!(!(1.0f64).less_than(1.0 + 1e-12) && 1.0f64.less_than(1.1));
+        assert!(1.0f64.less_than(f64::infinity()) && !f64::infinity().less_than(f64::infinity()));
+
+        // The tolerance follows the size of the numbers, however small or large they are.
+        assert!(1e-12f64.is_above_zero(1e-10) && 1e-12f64.is_zero(1.0));
+        assert!(1e-10f64.less_than(2e-10) && !1e10f64.less_than(1e10 + 1.0));
+        assert!(1e-7f32.less_than(2e-7) && 1e5f32.tolerance() > 0.0);
+        assert_eq!(f64::NAN.compare(&f64::infinity()), Ordering::Greater);
+
+        let mut weights = [Ordered(2.5), Ordered(f64::NAN), Ordered(-1.0)];
+        weights.sort();
+        assert_eq!(weights[0].0, -1.0);
+        assert_eq!(weights[1].0, 2.5);
+    }
+}